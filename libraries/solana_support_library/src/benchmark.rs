@@ -0,0 +1,174 @@
+use std::{
+  collections::{
+    BTreeMap,
+  },
+  env,
+  fs,
+  io,
+  path::{
+    Path,
+    PathBuf,
+  },
+};
+use super::{
+  context::{
+    TransactionOutcome,
+  },
+  error::{
+    FrameworkError,
+    FrameworkResult,
+  },
+};
+
+/// Environment variable selecting the `BaselineMode`: `update`, `warn` or `check`, the default.
+pub const BASELINE_MODE_VARIABLE: &str = "CU_BASELINE";
+
+/// What to do with compute units recorded against a baseline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BaselineMode {
+  /// Fails when a scenario has no baseline or exceeds it by more than the tolerance.
+  Check,
+  /// Prints a warning instead of failing.
+  Warn,
+  /// Rewrites the baseline with the recorded compute units.
+  Update,
+}
+
+impl BaselineMode {
+  /// Reads the mode from `CU_BASELINE`.
+  pub fn from_env() -> Self {
+    match env::var(BASELINE_MODE_VARIABLE).as_deref() {
+      Ok("update") => BaselineMode::Update,
+      Ok("warn") => BaselineMode::Warn,
+      _ => BaselineMode::Check,
+    }
+  }
+}
+
+/// Compute units consumed per named scenario compared with a checked-in baseline file,
+/// a JSON object mapping scenario names to compute units.
+#[derive(Clone, Debug)]
+pub struct ComputeUnitBaseline {
+  path: PathBuf,
+  mode: BaselineMode,
+  tolerance_percent: u64,
+  baseline: BTreeMap<String, u64>,
+  results: BTreeMap<String, u64>,
+}
+
+impl ComputeUnitBaseline {
+  pub fn new(path: &Path) -> Self {
+    Self::try_new(path)
+      .unwrap()
+  }
+
+  /// Reads the baseline at `path`, a missing file is an empty baseline. The mode is read
+  /// from `CU_BASELINE` and no tolerance is allowed by default.
+  pub fn try_new(path: &Path) -> FrameworkResult<Self> {
+    let baseline = match fs::read_to_string(path) {
+      Ok(content) => serde_json::from_str(&content)
+        .map_err(|err| FrameworkError::InvalidBaseline(format!("{}: {}", path.display(), err)))?,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+      Err(err) => return Err(err.into()),
+    };
+    Ok(ComputeUnitBaseline {
+      path: path.to_path_buf(),
+      mode: BaselineMode::from_env(),
+      tolerance_percent: 0,
+      baseline,
+      results: BTreeMap::new(),
+    })
+  }
+
+  pub fn set_mode(mut self, mode: BaselineMode) -> Self {
+    self.mode = mode;
+    self
+  }
+
+  /// Allows scenarios to exceed their baseline by `tolerance_percent` percent.
+  pub fn set_tolerance_percent(mut self, tolerance_percent: u64) -> Self {
+    self.tolerance_percent = tolerance_percent;
+    self
+  }
+
+  pub fn get_baseline(&self, scenario: &str) -> Option<u64> {
+    self.baseline.get(scenario).copied()
+  }
+
+  /// Returns the compute units recorded so far.
+  pub fn get_results(&self) -> &BTreeMap<String, u64> {
+    &self.results
+  }
+
+  pub fn record(&mut self, scenario: &str, compute_units: u64) {
+    self.try_record(scenario, compute_units)
+      .unwrap();
+  }
+
+  /// Records `compute_units` for `scenario` and compares them with its baseline. A scenario
+  /// without baseline fails in `BaselineMode::Check`.
+  pub fn try_record(&mut self, scenario: &str, compute_units: u64) -> FrameworkResult<()> {
+    self.results.insert(scenario.to_string(), compute_units);
+    if self.mode == BaselineMode::Update {
+      return Ok(());
+    }
+    let baseline = match self.get_baseline(scenario) {
+      Some(baseline) => baseline,
+      None => {
+        let error = FrameworkError::MissingBaseline(scenario.to_string());
+        if self.mode == BaselineMode::Warn {
+          eprintln!("warning: {}", error);
+          return Ok(());
+        }
+        return Err(error);
+      },
+    };
+    let max_units = baseline.saturating_add(baseline.saturating_mul(self.tolerance_percent) / 100);
+    if compute_units <= max_units {
+      return Ok(());
+    }
+    let error = FrameworkError::ComputeUnitsExceeded {
+      scenario: scenario.to_string(),
+      baseline,
+      compute_units,
+    };
+    if self.mode == BaselineMode::Warn {
+      eprintln!("warning: {}", error);
+      return Ok(());
+    }
+    Err(error)
+  }
+
+  pub fn record_outcome(&mut self, scenario: &str, outcome: &TransactionOutcome) {
+    self.try_record_outcome(scenario, outcome)
+      .unwrap();
+  }
+
+  /// Records the compute units consumed by a successful transaction.
+  pub fn try_record_outcome(&mut self, scenario: &str, outcome: &TransactionOutcome) -> FrameworkResult<()> {
+    outcome.result.clone()?;
+    self.try_record(scenario, outcome.compute_units_consumed)
+  }
+
+  pub fn finish(self) {
+    self.try_finish()
+      .unwrap();
+  }
+
+  /// Writes the recorded compute units to the baseline file in `BaselineMode::Update`, keeping
+  /// the scenarios that were not recorded. Does nothing in the other modes.
+  pub fn try_finish(self) -> FrameworkResult<()> {
+    if self.mode != BaselineMode::Update {
+      return Ok(());
+    }
+    let mut baseline = self.baseline;
+    baseline.extend(self.results);
+    let content = serde_json::to_string_pretty(&baseline)
+      .map_err(|err| FrameworkError::InvalidBaseline(err.to_string()))?;
+    if let Some(directory) = self.path.parent() {
+      fs::create_dir_all(directory)?;
+    }
+    fs::write(&self.path, content + "\n")?;
+    Ok(())
+  }
+}
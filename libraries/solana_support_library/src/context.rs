@@ -0,0 +1,1570 @@
+use std::{
+  fmt::{
+    Debug,
+  },
+  io,
+  path::{
+    Path,
+  },
+  time::{
+    SystemTime,
+    UNIX_EPOCH,
+  },
+};
+use anchor_lang::{
+  AccountDeserialize,
+  AccountSerialize,
+  AnchorDeserialize,
+  Owner,
+};
+use solana_program_runtime::{
+  compute_budget::{
+    DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT,
+    MAX_COMPUTE_UNIT_LIMIT,
+  },
+  invoke_context::{
+    ProcessInstructionWithContext,
+  },
+};
+use solana_program_test::{
+  BanksClientError,
+  ProgramTest,
+  ProgramTestBanksClientExt,
+  ProgramTestContext,
+  ProgramTestError,
+};
+use solana_sdk::{
+  account::{
+    Account,
+    AccountSharedData,
+  },
+  clock::{
+    Clock,
+    DEFAULT_MS_PER_SLOT,
+  },
+  compute_budget::{
+    self,
+    ComputeBudgetInstruction,
+  },
+  epoch_schedule::{
+    EpochSchedule,
+  },
+  hash::{
+    Hash,
+  },
+  instruction::{
+    Instruction,
+    InstructionError,
+  },
+  program_error::{
+    ProgramError,
+  },
+  program_option::{
+    COption,
+  },
+  program_pack::{
+    IsInitialized,
+    Pack,
+  },
+  pubkey::{
+    Pubkey,
+  },
+  rent::{
+    Rent,
+  },
+  signature::{
+    Keypair,
+    Signer,
+  },
+  transaction::{
+    Transaction,
+    TransactionError,
+    uses_durable_nonce,
+  },
+  transaction_context::{
+    TransactionReturnData,
+  },
+};
+use super::{
+  error::{
+    FrameworkError,
+    FrameworkResult,
+  },
+  fixture::{
+    read_account_fixture,
+    read_account_fixtures,
+  },
+  log_message::{
+    find_anchor_error,
+    parse_log_messages,
+  },
+  spl_atoken::{
+    get_associated_token_account_address_with_program_id,
+  },
+  spl_token::{
+    AccountState,
+    ID as TOKEN_PROGRAM_ID,
+    TokenAccount,
+    TokenMint,
+  },
+  system::{
+    try_get_nonce,
+  },
+};
+
+/// Result of an executed or simulated transaction together with its metadata.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransactionOutcome {
+  /// `Ok` if all instructions succeeded.
+  pub result: Result<(), TransactionError>,
+  /// Log messages in the order the runtime emitted them.
+  pub log_messages: Vec<String>,
+  /// Compute units consumed by the whole transaction.
+  pub compute_units_consumed: u64,
+  /// Data set by the last `set_return_data` call, if any.
+  pub return_data: Option<TransactionReturnData>,
+}
+
+impl TransactionOutcome {
+  /// Checks if the transaction succeeded
+  pub fn is_success(&self) -> bool {
+    self.result.is_ok()
+  }
+  /// Returns the payload set via `set_return_data` by the given program
+  pub fn return_data_of(&self, program_id: &Pubkey) -> Option<&[u8]> {
+    match &self.return_data {
+      Some(return_data) if return_data.program_id == *program_id => Some(&return_data.data),
+      _ => None,
+    }
+  }
+}
+
+/// Builds a `ProgramTestContext` whose ledger already contains the given programs,
+/// accounts and clock.
+pub struct TestContextBuilder {
+  prefer_bpf: bool,
+  programs: Vec<(String, Pubkey, Option<ProcessInstructionWithContext>)>,
+  accounts: Vec<(Pubkey, Account)>,
+  compute_max_units: Option<u64>,
+  slot: Option<u64>,
+  unix_timestamp: Option<i64>,
+}
+
+impl Default for TestContextBuilder {
+  fn default() -> Self {
+    TestContextBuilder {
+      prefer_bpf: true,
+      programs: Vec::new(),
+      accounts: Vec::new(),
+      compute_max_units: None,
+      slot: None,
+      unix_timestamp: None,
+    }
+  }
+}
+
+impl TestContextBuilder {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Loads programs from their `.so` files even if a native processor is available, `true` by default.
+  pub fn prefer_bpf(mut self, prefer_bpf: bool) -> Self {
+    self.prefer_bpf = prefer_bpf;
+    self
+  }
+
+  /// Adds a BPF program loaded from `<name>.so`.
+  pub fn add_program(mut self, name: &str, program_id: &Pubkey) -> Self {
+    self.programs.push((name.to_string(), *program_id, None));
+    self
+  }
+
+  /// Adds a program running natively, `processor` is built with `solana_program_test::processor!`.
+  pub fn add_native_program(
+    mut self,
+    name: &str,
+    program_id: &Pubkey,
+    processor: Option<ProcessInstructionWithContext>,
+  ) -> Self {
+    self.programs.push((name.to_string(), *program_id, processor));
+    self
+  }
+
+  pub fn add_account(mut self, address: &Pubkey, account: Account) -> Self {
+    self.accounts.push((*address, account));
+    self
+  }
+
+  /// Adds an account funded with the rent-exempt minimum for `data`.
+  pub fn add_account_with_data(
+    self,
+    address: &Pubkey,
+    owner: &Pubkey,
+    data: &[u8],
+    executable: bool,
+  ) -> Self {
+    let account = Account {
+      lamports: Rent::default().minimum_balance(data.len()),
+      data: data.to_vec(),
+      owner: *owner,
+      executable,
+      rent_epoch: 0,
+    };
+    self.add_account(address, account)
+  }
+
+  pub fn add_account_fixture(self, path: &Path) -> Self {
+    self.try_add_account_fixture(path)
+      .unwrap()
+  }
+
+  /// Adds an account dumped by `solana account <ADDRESS> --output json`.
+  pub fn try_add_account_fixture(self, path: &Path) -> FrameworkResult<Self> {
+    let (address, account) = read_account_fixture(path)?;
+    Ok(self.add_account(&address, account))
+  }
+
+  pub fn add_account_fixtures(self, directory: &Path) -> Self {
+    self.try_add_account_fixtures(directory)
+      .unwrap()
+  }
+
+  /// Adds every `*.json` account dump found in `directory`.
+  pub fn try_add_account_fixtures(mut self, directory: &Path) -> FrameworkResult<Self> {
+    let fixtures = read_account_fixtures(directory)?;
+    self.accounts.extend(fixtures);
+    Ok(self)
+  }
+
+  pub fn add_account_with_lamports(
+    self,
+    address: &Pubkey,
+    owner: &Pubkey,
+    lamports: u64,
+  ) -> Self {
+    let account = Account {
+      lamports,
+      data: Vec::new(),
+      owner: *owner,
+      executable: false,
+      rent_epoch: 0,
+    };
+    self.add_account(address, account)
+  }
+
+  pub fn add_token_mint(
+    self,
+    address: &Pubkey,
+    decimals: u8,
+    mint_authority: Option<&Pubkey>,
+    freeze_authority: Option<&Pubkey>,
+    supply: u64,
+  ) -> Self {
+    self.add_token_mint_with_program_id(address, decimals, mint_authority, freeze_authority, supply, &TOKEN_PROGRAM_ID)
+  }
+
+  /// Adds a mint owned by `token_program_id`, SPL Token or Token-2022 without extensions.
+  pub fn add_token_mint_with_program_id(
+    self,
+    address: &Pubkey,
+    decimals: u8,
+    mint_authority: Option<&Pubkey>,
+    freeze_authority: Option<&Pubkey>,
+    supply: u64,
+    token_program_id: &Pubkey,
+  ) -> Self {
+    let token_mint = TokenMint {
+      mint_authority: COption::from(mint_authority.copied()),
+      supply,
+      decimals,
+      is_initialized: true,
+      freeze_authority: COption::from(freeze_authority.copied()),
+    };
+    let mut data = vec![0u8; TokenMint::LEN];
+    TokenMint::pack(token_mint, &mut data).unwrap();
+    self.add_account_with_data(address, token_program_id, &data, false)
+  }
+
+  pub fn add_token_account(
+    self,
+    address: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+  ) -> Self {
+    self.add_token_account_with_program_id(address, mint, owner, amount, &TOKEN_PROGRAM_ID)
+  }
+
+  /// Adds a token account owned by `token_program_id`, SPL Token or Token-2022 without extensions.
+  pub fn add_token_account_with_program_id(
+    self,
+    address: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    token_program_id: &Pubkey,
+  ) -> Self {
+    let token_account = TokenAccount {
+      mint: *mint,
+      owner: *owner,
+      amount,
+      state: AccountState::Initialized,
+      ..TokenAccount::default()
+    };
+    let mut data = vec![0u8; TokenAccount::LEN];
+    TokenAccount::pack(token_account, &mut data).unwrap();
+    self.add_account_with_data(address, token_program_id, &data, false)
+  }
+
+  /// Adds the associated token account of `owner` for `mint`.
+  pub fn add_associated_token_account(
+    self,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+  ) -> Self {
+    self.add_associated_token_account_with_program_id(owner, mint, amount, &TOKEN_PROGRAM_ID)
+  }
+
+  /// Adds the associated token account of `owner` for a mint owned by `token_program_id`.
+  pub fn add_associated_token_account_with_program_id(
+    self,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    token_program_id: &Pubkey,
+  ) -> Self {
+    let address = get_associated_token_account_address_with_program_id(owner, mint, token_program_id);
+    self.add_token_account_with_program_id(&address, mint, owner, amount, token_program_id)
+  }
+
+  /// Sets the compute unit limit of each transaction.
+  pub fn set_compute_max_units(mut self, compute_max_units: u64) -> Self {
+    self.compute_max_units = Some(compute_max_units);
+    self
+  }
+
+  /// Warps the bank to `slot` once started.
+  pub fn set_slot(mut self, slot: u64) -> Self {
+    self.slot = Some(slot);
+    self
+  }
+
+  /// Overrides `Clock::unix_timestamp` once started.
+  pub fn set_unix_timestamp(mut self, unix_timestamp: i64) -> Self {
+    self.unix_timestamp = Some(unix_timestamp);
+    self
+  }
+
+  pub async fn build(self) -> ProgramTestContext {
+    let mut program_test = ProgramTest::default();
+    program_test.prefer_bpf(self.prefer_bpf);
+    for (name, program_id, processor) in self.programs {
+      program_test.add_program(
+        &name,
+        program_id,
+        processor,
+      );
+    }
+    for (address, account) in self.accounts {
+      program_test.add_account(address, account);
+    }
+    if let Some(compute_max_units) = self.compute_max_units {
+      program_test.set_compute_max_units(compute_max_units);
+    }
+
+    let mut context = program_test.start_with_context().await;
+    if let Some(slot) = self.slot {
+      context.warp_to_slot(slot).unwrap();
+    }
+    if let Some(unix_timestamp) = self.unix_timestamp {
+      let mut clock = context.banks_client.get_sysvar::<Clock>()
+        .await.unwrap();
+      clock.unix_timestamp = unix_timestamp;
+      context.set_sysvar(&clock);
+    }
+    context
+  }
+}
+
+/// How the execution helpers pick the blockhash transactions are signed with.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BlockhashPolicy {
+  /// Signs with `context.last_blockhash` as is, sending the same transaction twice fails
+  /// as already processed.
+  Reuse,
+  /// Fetches a new blockhash and sends once more if the transaction was rejected as already
+  /// processed or for an expired blockhash, the default.
+  #[default]
+  RefreshWhenNeeded,
+  /// Fetches a new blockhash before every transaction.
+  AlwaysRefresh,
+  /// Reports a transaction rejected as already processed with `TransactionError::AlreadyProcessed`,
+  /// fetches a new blockhash and sends once more if the blockhash expired.
+  FailOnDuplicate,
+}
+
+/// Options of the `*_with_options` execution helpers, the other helpers use the defaults.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TransactionOptions {
+  pub blockhash_policy: BlockhashPolicy,
+  pub compute_budget: ComputeBudget,
+}
+
+impl TransactionOptions {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn set_blockhash_policy(
+    mut self,
+    blockhash_policy: BlockhashPolicy,
+  ) -> Self {
+    self.blockhash_policy = blockhash_policy;
+    self
+  }
+
+  pub fn set_compute_budget(
+    mut self,
+    compute_budget: ComputeBudget,
+  ) -> Self {
+    self.compute_budget = compute_budget;
+    self
+  }
+}
+
+/// Compute budget requests prepended to the transactions sent with `TransactionOptions`. The
+/// helpers report instruction errors at the indexes of the transaction's own instructions,
+/// errors of the compute budget instructions themselves keep their index.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ComputeBudget {
+  /// Compute unit limit of the whole transaction.
+  pub unit_limit: Option<u32>,
+  /// Priority fee in micro-lamports per compute unit.
+  pub unit_price: Option<u64>,
+  /// Heap size in bytes, a multiple of 1024 of at most 256 KiB.
+  pub heap_frame_bytes: Option<u32>,
+}
+
+impl ComputeBudget {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn set_unit_limit(
+    mut self,
+    unit_limit: u32,
+  ) -> Self {
+    self.unit_limit = Some(unit_limit);
+    self
+  }
+
+  pub fn set_unit_price(
+    mut self,
+    unit_price: u64,
+  ) -> Self {
+    self.unit_price = Some(unit_price);
+    self
+  }
+
+  pub fn set_heap_frame_bytes(
+    mut self,
+    heap_frame_bytes: u32,
+  ) -> Self {
+    self.heap_frame_bytes = Some(heap_frame_bytes);
+    self
+  }
+
+  /// Returns the `ComputeBudgetInstruction`s requesting this budget.
+  pub fn instructions(
+    &self,
+  ) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    if let Some(unit_limit) = self.unit_limit {
+      instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(unit_limit));
+    }
+    if let Some(unit_price) = self.unit_price {
+      instructions.push(ComputeBudgetInstruction::set_compute_unit_price(unit_price));
+    }
+    if let Some(heap_frame_bytes) = self.heap_frame_bytes {
+      instructions.push(ComputeBudgetInstruction::request_heap_frame(heap_frame_bytes));
+    }
+    instructions
+  }
+
+  /// Computes the lamports the unit price adds to the fee of a transaction with
+  /// `instruction_count` instructions, not counting the compute budget ones. Without a unit
+  /// limit the runtime charges for its default limit per instruction.
+  pub fn prioritization_fee(
+    &self,
+    instruction_count: usize,
+  ) -> u64 {
+    let unit_price = match self.unit_price {
+      Some(unit_price) => unit_price,
+      None => return 0,
+    };
+    let unit_limit = self.unit_limit.unwrap_or_else(|| {
+      (instruction_count as u32)
+        .saturating_mul(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT)
+        .min(MAX_COMPUTE_UNIT_LIMIT)
+    });
+    let fee = (unit_price as u128 * unit_limit as u128 + 999_999) / 1_000_000;
+    fee as u64
+  }
+}
+
+/// Moves the bank forward keeping `Clock` consistent with the `EpochSchedule`: slots,
+/// epochs and wall time advance together at `slot_duration_ms` per slot. Wall time is
+/// measured from slot 0, so advancing one slot at a time does not drift.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClockController {
+  slot_duration_ms: u64,
+}
+
+impl Default for ClockController {
+  fn default() -> Self {
+    ClockController {
+      slot_duration_ms: DEFAULT_MS_PER_SLOT,
+    }
+  }
+}
+
+impl ClockController {
+  pub fn new(
+    slot_duration_ms: u64,
+  ) -> Self {
+    assert!(slot_duration_ms > 0, "slot_duration_ms must be positive");
+    ClockController {
+      slot_duration_ms,
+    }
+  }
+
+  pub fn slot_duration_ms(
+    &self,
+  ) -> u64 {
+    self.slot_duration_ms
+  }
+
+  pub async fn forward_slots(
+    &self,
+    context: &mut ProgramTestContext,
+    slot_count: u64,
+  ) -> Clock {
+    self.try_forward_slots(context, slot_count)
+      .await.unwrap()
+  }
+
+  pub async fn try_forward_slots(
+    &self,
+    context: &mut ProgramTestContext,
+    slot_count: u64,
+  ) -> FrameworkResult<Clock> {
+    let clock = context.banks_client.get_sysvar::<Clock>()
+      .await?;
+    let new_slot = clock.slot.checked_add(slot_count)
+      .ok_or(ProgramError::InvalidArgument)?;
+    self.try_warp_to_slot(context, new_slot)
+      .await
+  }
+
+  pub async fn forward_time(
+    &self,
+    context: &mut ProgramTestContext,
+    seconds: u64,
+  ) -> Clock {
+    self.try_forward_time(context, seconds)
+      .await.unwrap()
+  }
+
+  /// Advances wall time by exactly `seconds`, and slots by as many as that lasts, rounded up.
+  pub async fn try_forward_time(
+    &self,
+    context: &mut ProgramTestContext,
+    seconds: u64,
+  ) -> FrameworkResult<Clock> {
+    let clock = context.banks_client.get_sysvar::<Clock>()
+      .await?;
+    if seconds == 0 {
+      return Ok(clock);
+    }
+    let duration_ms = seconds.checked_mul(1_000)
+      .ok_or(ProgramError::InvalidArgument)?;
+    let slot_count = (duration_ms + self.slot_duration_ms - 1) / self.slot_duration_ms;
+    let new_slot = clock.slot.checked_add(slot_count)
+      .ok_or(ProgramError::InvalidArgument)?;
+    let new_timestamp = clock.unix_timestamp.checked_add(seconds as i64)
+      .ok_or(ProgramError::InvalidArgument)?;
+    self.try_advance(context, &clock, new_slot, new_timestamp)
+      .await
+  }
+
+  pub async fn warp_to_slot(
+    &self,
+    context: &mut ProgramTestContext,
+    slot: u64,
+  ) -> Clock {
+    self.try_warp_to_slot(context, slot)
+      .await.unwrap()
+  }
+
+  pub async fn try_warp_to_slot(
+    &self,
+    context: &mut ProgramTestContext,
+    slot: u64,
+  ) -> FrameworkResult<Clock> {
+    let clock = context.banks_client.get_sysvar::<Clock>()
+      .await?;
+    if slot < clock.slot {
+      return Err(ProgramTestError::InvalidWarpSlot.into());
+    }
+    let new_timestamp = clock.unix_timestamp.checked_add(self.get_elapsed_seconds(clock.slot, slot))
+      .ok_or(ProgramError::InvalidArgument)?;
+    self.try_advance(context, &clock, slot, new_timestamp)
+      .await
+  }
+
+  pub async fn warp_to_epoch(
+    &self,
+    context: &mut ProgramTestContext,
+    epoch: u64,
+  ) -> Clock {
+    self.try_warp_to_epoch(context, epoch)
+      .await.unwrap()
+  }
+
+  /// Warps to the first slot of `epoch`.
+  pub async fn try_warp_to_epoch(
+    &self,
+    context: &mut ProgramTestContext,
+    epoch: u64,
+  ) -> FrameworkResult<Clock> {
+    let epoch_schedule = context.banks_client.get_sysvar::<EpochSchedule>()
+      .await?;
+    let slot = epoch_schedule.get_first_slot_in_epoch(epoch);
+    self.try_warp_to_slot(context, slot)
+      .await
+  }
+
+  pub async fn set_clock(
+    &self,
+    context: &mut ProgramTestContext,
+    slot: u64,
+    unix_timestamp: i64,
+  ) -> Clock {
+    self.try_set_clock(context, slot, unix_timestamp)
+      .await.unwrap()
+  }
+
+  /// Moves to `slot`, which must not be behind the bank, and sets the wall time to
+  /// `unix_timestamp`.
+  pub async fn try_set_clock(
+    &self,
+    context: &mut ProgramTestContext,
+    slot: u64,
+    unix_timestamp: i64,
+  ) -> FrameworkResult<Clock> {
+    let clock = context.banks_client.get_sysvar::<Clock>()
+      .await?;
+    if slot < clock.slot {
+      return Err(ProgramTestError::InvalidWarpSlot.into());
+    }
+    self.try_advance(context, &clock, slot, unix_timestamp)
+      .await
+  }
+
+  /// Whole seconds between the starts of `from_slot` and `to_slot`, both measured from slot 0.
+  fn get_elapsed_seconds(
+    &self,
+    from_slot: u64,
+    to_slot: u64,
+  ) -> i64 {
+    let get_seconds = |slot: u64| slot as u128 * self.slot_duration_ms as u128 / 1_000;
+    (get_seconds(to_slot) - get_seconds(from_slot)) as i64
+  }
+
+  async fn try_advance(
+    &self,
+    context: &mut ProgramTestContext,
+    clock: &Clock,
+    slot: u64,
+    unix_timestamp: i64,
+  ) -> FrameworkResult<Clock> {
+    let epoch_schedule = context.banks_client.get_sysvar::<EpochSchedule>()
+      .await?;
+    if slot > clock.slot {
+      context.warp_to_slot(slot)?;
+    }
+    let epoch = epoch_schedule.get_epoch(slot);
+    let epoch_start_timestamp = if epoch == clock.epoch {
+      clock.epoch_start_timestamp
+    } else {
+      let first_slot = epoch_schedule.get_first_slot_in_epoch(epoch);
+      unix_timestamp.saturating_sub(self.get_elapsed_seconds(first_slot, slot))
+    };
+    let new_clock = Clock {
+      slot,
+      epoch_start_timestamp,
+      epoch,
+      leader_schedule_epoch: epoch_schedule.get_leader_schedule_epoch(slot),
+      unix_timestamp,
+    };
+    context.set_sysvar(&new_clock);
+    Ok(new_clock)
+  }
+}
+
+pub fn assert_transaction_result<T, E: Into<FrameworkError>>(
+  result: Result<T, E>,
+  is_success: bool,
+  expected_error_message: &str,
+) {
+  assert!(result.is_ok() == is_success);
+  if is_success {
+    return;
+  }
+  let error: FrameworkError = result.err().unwrap().into();
+  let error_message = error.to_string();
+  assert!(error_message.contains(&expected_error_message));
+}
+
+pub fn assert_instruction_error<T, E: Into<FrameworkError>>(
+  result: Result<T, E>,
+  instruction_index: u8,
+  error_code: u32,
+) {
+  assert!(result.is_err());
+  let error: FrameworkError = result.err().unwrap().into();
+  let error = error.transaction_error()
+    .unwrap();
+  assert_eq!(
+    error,
+    TransactionError::InstructionError(
+      instruction_index,
+      InstructionError::Custom(error_code),
+    ),
+  );
+}
+
+/// Checks that the whole transaction consumed at most `max_units` compute units.
+pub fn assert_compute_units_within(
+  outcome: &TransactionOutcome,
+  max_units: u64,
+) {
+  assert!(
+    outcome.compute_units_consumed <= max_units,
+    "transaction consumed {} compute units, budget is {}",
+    outcome.compute_units_consumed,
+    max_units,
+  );
+}
+
+/// Checks that the top-level instruction at `instruction_index`, including its CPI calls,
+/// consumed at most `max_units` compute units. Compute budget instructions are not counted
+/// in `instruction_index`.
+pub fn assert_instruction_compute_units_within(
+  outcome: &TransactionOutcome,
+  instruction_index: u8,
+  max_units: u64,
+) {
+  let invocations = parse_log_messages(&outcome.log_messages);
+  let invocation = invocations.iter()
+    .filter(|invocation| !compute_budget::check_id(&invocation.program_id))
+    .nth(instruction_index as usize)
+    .unwrap_or_else(|| panic!("instruction {} not found in log messages", instruction_index));
+  let compute_units_consumed = invocation.compute_units_consumed
+    .unwrap_or_else(|| panic!("instruction {} did not report its compute units", instruction_index));
+  assert!(
+    compute_units_consumed <= max_units,
+    "instruction {} consumed {} compute units, budget is {}",
+    instruction_index,
+    compute_units_consumed,
+    max_units,
+  );
+}
+
+/// Asserts the transaction failed at `instruction_index` with the given Anchor error,
+/// either a program's `#[error_code]` enum or `anchor_lang::error::ErrorCode`.
+pub fn assert_anchor_error<T, E: Into<FrameworkError>, C: Into<u32> + Debug>(
+  result: Result<T, E>,
+  instruction_index: u8,
+  expected_error: C,
+) {
+  assert!(result.is_err(), "transaction succeeded, expected {:?}", expected_error);
+  let error: FrameworkError = result.err().unwrap().into();
+  let log_messages = error.log_messages()
+    .unwrap_or(&[]);
+  check_anchor_error(
+    error.transaction_error(),
+    log_messages,
+    instruction_index,
+    expected_error,
+  );
+}
+
+/// Same as `assert_anchor_error` but reads the error and logs from a `TransactionOutcome`.
+pub fn assert_transaction_anchor_error<C: Into<u32> + Debug>(
+  outcome: &TransactionOutcome,
+  instruction_index: u8,
+  expected_error: C,
+) {
+  assert!(!outcome.is_success(), "transaction succeeded, expected {:?}", expected_error);
+  check_anchor_error(
+    outcome.result.clone().err(),
+    &outcome.log_messages,
+    instruction_index,
+    expected_error,
+  );
+}
+
+pub async fn create_context(
+  programs: &[(&str, Pubkey)]
+) -> ProgramTestContext {
+  let mut context_builder = TestContextBuilder::new();
+  for i in 0..programs.len() {
+    let program = &programs[i];
+    context_builder = context_builder.add_program(
+      program.0,
+      &program.1,
+    );
+  }
+  context_builder.build().await
+}
+
+pub async fn get_anchor_account<T: AccountDeserialize + Owner>(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> Option<T> {
+  try_get_anchor_account(context, address)
+    .await.unwrap()
+}
+
+/// Reads an Anchor `#[account]`, checking its owner and discriminator. Returns `None` if
+/// the account does not exist.
+pub async fn try_get_anchor_account<T: AccountDeserialize + Owner>(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> FrameworkResult<Option<T>> {
+  let account = match get_account_with_owner(context, address, &T::owner()).await? {
+    Some(account) => account,
+    None => return Ok(None),
+  };
+  let value = T::try_deserialize(&mut account.data.as_slice())
+    .map_err(|err| FrameworkError::InvalidAccountData {
+      address: *address,
+      reason: err.to_string(),
+    })?;
+  Ok(Some(value))
+}
+
+pub async fn get_borsh_account<T: AnchorDeserialize>(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+  expected_owner: &Pubkey,
+) -> Option<T> {
+  try_get_borsh_account(context, address, expected_owner)
+    .await.unwrap()
+}
+
+/// Reads a Borsh-serialized account owned by `expected_owner`, ignoring trailing bytes.
+/// Returns `None` if the account does not exist.
+pub async fn try_get_borsh_account<T: AnchorDeserialize>(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+  expected_owner: &Pubkey,
+) -> FrameworkResult<Option<T>> {
+  let account = match get_account_with_owner(context, address, expected_owner).await? {
+    Some(account) => account,
+    None => return Ok(None),
+  };
+  let value = T::deserialize(&mut account.data.as_slice())
+    .map_err(|err| FrameworkError::InvalidAccountData {
+      address: *address,
+      reason: err.to_string(),
+    })?;
+  Ok(Some(value))
+}
+
+pub fn get_current_timestamp(
+) -> i64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .unwrap()
+    .as_secs() as i64
+}
+
+pub async fn get_packed_account<T: Pack + IsInitialized>(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+  expected_owner: &Pubkey,
+) -> Option<T> {
+  try_get_packed_account(context, address, expected_owner)
+    .await.unwrap()
+}
+
+/// Reads an initialized account in the `Pack` layout owned by `expected_owner`, i.e. an SPL
+/// `TokenMint`. Returns `None` if the account does not exist.
+pub async fn try_get_packed_account<T: Pack + IsInitialized>(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+  expected_owner: &Pubkey,
+) -> FrameworkResult<Option<T>> {
+  let account = match get_account_with_owner(context, address, expected_owner).await? {
+    Some(account) => account,
+    None => return Ok(None),
+  };
+  let value = T::unpack(&account.data)
+    .map_err(|err| FrameworkError::InvalidAccountData {
+      address: *address,
+      reason: err.to_string(),
+    })?;
+  Ok(Some(value))
+}
+
+pub fn get_payer(
+  context: &ProgramTestContext,
+) -> Keypair {
+  Keypair::from_bytes(&context.payer.to_bytes())
+    .unwrap()
+}
+
+pub async fn get_rent(
+  context: &mut ProgramTestContext,
+) -> Rent {
+  try_get_rent(context)
+    .await.unwrap()
+}
+
+pub async fn try_get_rent(
+  context: &mut ProgramTestContext,
+) -> FrameworkResult<Rent> {
+  let rent = context.banks_client
+    .get_rent()
+    .await?;
+  Ok(rent)
+}
+
+pub async fn refresh_blockhash(
+  context: &mut ProgramTestContext,
+) -> Hash {
+  try_refresh_blockhash(context)
+    .await.unwrap()
+}
+
+/// Waits for a blockhash newer than `context.last_blockhash`, the helpers sign with it from
+/// then on.
+pub async fn try_refresh_blockhash(
+  context: &mut ProgramTestContext,
+) -> FrameworkResult<Hash> {
+  let blockhash = get_new_blockhash(context)
+    .await?;
+  Ok(blockhash)
+}
+
+pub async fn forward_slot(
+  context: &mut ProgramTestContext,
+  slot_count: u64,
+) {
+  try_forward_slot(context, slot_count)
+    .await.unwrap();
+}
+
+/// Advances `slot_count` slots with the default `ClockController`.
+pub async fn try_forward_slot(
+  context: &mut ProgramTestContext,
+  slot_count: u64,
+) -> FrameworkResult<()> {
+  ClockController::default()
+    .try_forward_slots(context, slot_count)
+    .await?;
+  Ok(())
+}
+
+pub async fn forward_timestamp(
+  context: &mut ProgramTestContext,
+  timestamp_count: i64,
+) {
+  try_forward_timestamp(context, timestamp_count)
+    .await.unwrap();
+}
+
+/// Rewrites `Clock::unix_timestamp` only, see `ClockController::forward_time` to advance
+/// slots as well.
+pub async fn try_forward_timestamp(
+  context: &mut ProgramTestContext,
+  timestamp_count: i64,
+) -> FrameworkResult<()> {
+  let clock= context.banks_client.get_sysvar::<Clock>()
+    .await?;
+  let mut new_clock = clock.clone();
+  new_clock.unix_timestamp = clock.unix_timestamp.checked_add(timestamp_count)
+    .ok_or(ProgramError::InvalidArgument)?;
+  context.set_sysvar(&new_clock);
+  Ok(())
+}
+
+pub async fn process_transaction(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+) {
+  try_process_transaction(context, payer, instructions, signers)
+    .await.unwrap();
+}
+
+pub async fn try_process_transaction(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+) -> FrameworkResult<()> {
+  process_transaction2(context, payer, instructions, signers)
+    .await?;
+  Ok(())
+}
+
+pub async fn process_transaction_with_options(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+  options: &TransactionOptions,
+) {
+  try_process_transaction_with_options(context, payer, instructions, signers, options)
+    .await.unwrap();
+}
+
+pub async fn try_process_transaction_with_options(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+  options: &TransactionOptions,
+) -> FrameworkResult<()> {
+  try_send_transaction(context, payer, instructions, signers, options)
+    .await?;
+  Ok(())
+}
+
+pub async fn execute_transaction(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+) -> TransactionOutcome {
+  try_execute_transaction(context, payer, instructions, signers)
+    .await.unwrap()
+}
+
+/// Processes the transaction and returns its outcome. Only transport failures
+/// are returned as `Err`, a failed transaction is reported in `TransactionOutcome::result`.
+pub async fn try_execute_transaction(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+) -> FrameworkResult<TransactionOutcome> {
+  try_execute_transaction_with_options(
+    context,
+    payer,
+    instructions,
+    signers,
+    &TransactionOptions::default(),
+  ).await
+}
+
+pub async fn execute_transaction_with_options(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+  options: &TransactionOptions,
+) -> TransactionOutcome {
+  try_execute_transaction_with_options(context, payer, instructions, signers, options)
+    .await.unwrap()
+}
+
+pub async fn try_execute_transaction_with_options(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+  options: &TransactionOptions,
+) -> FrameworkResult<TransactionOutcome> {
+  let tx = try_sign_transaction(context, payer, instructions, signers, options)
+    .await?;
+  let mut outcome = try_execute_signed_transaction(context, tx)
+    .await?;
+  if should_refresh_blockhash(options, outcome.result.as_ref().err()) {
+    get_new_blockhash(context)
+      .await?;
+    let tx = try_sign_transaction(context, payer, instructions, signers, options)
+      .await?;
+    outcome = try_execute_signed_transaction(context, tx)
+      .await?;
+  }
+  if let Err(error) = &mut outcome.result {
+    shift_instruction_error(error, options);
+  }
+  Ok(outcome)
+}
+
+pub async fn execute_signed_transaction(
+  context: &mut ProgramTestContext,
+  tx: Transaction,
+) -> TransactionOutcome {
+  try_execute_signed_transaction(context, tx)
+    .await.unwrap()
+}
+
+/// Processes a transaction signed by the caller as is. The logs, compute units and return
+/// data come from simulating it first, the banks client does not return them for processed
+/// transactions. The banks server cannot process a transaction using a durable nonce, one
+/// signed with an advanced nonce fails with `TransactionError::BlockhashNotFound` as on a
+/// validator, one signed with the current nonce is returned as `Err`.
+pub async fn try_execute_signed_transaction(
+  context: &mut ProgramTestContext,
+  tx: Transaction,
+) -> FrameworkResult<TransactionOutcome> {
+  let txn_error = match is_transaction_processed(context, &tx).await? {
+    true => Some(TransactionError::AlreadyProcessed),
+    false => try_check_durable_nonce(context, &tx).await?,
+  };
+  if let Some(txn_error) = txn_error {
+    return Ok(TransactionOutcome {
+      result: Err(txn_error),
+      log_messages: Vec::new(),
+      compute_units_consumed: 0,
+      return_data: None,
+    });
+  }
+  let mut outcome = try_simulate_signed_transaction(context, tx.clone())
+    .await?;
+  if outcome.is_success() {
+    outcome.result = match context.banks_client.process_transaction(tx).await {
+      Ok(()) => Ok(()),
+      Err(BanksClientError::TransactionError(err)) => Err(err),
+      Err(err) => return Err(err.into()),
+    };
+  }
+  Ok(outcome)
+}
+
+/// Overwrites the account at `address`, bypassing the runtime.
+pub fn set_account(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+  account: &Account,
+) {
+  context.set_account(address, &AccountSharedData::from(account.clone()));
+}
+
+pub async fn set_account_with_data(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+  owner: &Pubkey,
+  data: &[u8],
+  executable: bool,
+) {
+  try_set_account_with_data(context, address, owner, data, executable)
+    .await.unwrap();
+}
+
+/// Overwrites the account at `address` with `data`, funded with the rent-exempt minimum.
+pub async fn try_set_account_with_data(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+  owner: &Pubkey,
+  data: &[u8],
+  executable: bool,
+) -> FrameworkResult<()> {
+  let rent = try_get_rent(context).await?;
+  let account = Account {
+    lamports: rent.minimum_balance(data.len()),
+    data: data.to_vec(),
+    owner: *owner,
+    executable,
+    rent_epoch: 0,
+  };
+  set_account(context, address, &account);
+  Ok(())
+}
+
+pub async fn set_anchor_account<T: AccountSerialize + Owner>(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+  value: &T,
+) {
+  try_set_anchor_account(context, address, value)
+    .await.unwrap();
+}
+
+/// Writes an Anchor `#[account]` with its discriminator, owned by its program.
+pub async fn try_set_anchor_account<T: AccountSerialize + Owner>(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+  value: &T,
+) -> FrameworkResult<()> {
+  let mut data = Vec::new();
+  value.try_serialize(&mut data)
+    .map_err(|err| FrameworkError::InvalidAccountData {
+      address: *address,
+      reason: err.to_string(),
+    })?;
+  try_set_account_with_data(context, address, &T::owner(), &data, false)
+    .await
+}
+
+pub async fn set_token_account(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+  token_account: &TokenAccount,
+) {
+  try_set_token_account(context, address, token_account)
+    .await.unwrap();
+}
+
+/// Writes an SPL Token account in any state, i.e. frozen or delegated.
+pub async fn try_set_token_account(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+  token_account: &TokenAccount,
+) -> FrameworkResult<()> {
+  let mut data = vec![0u8; TokenAccount::LEN];
+  TokenAccount::pack(*token_account, &mut data)?;
+  try_set_account_with_data(context, address, &TOKEN_PROGRAM_ID, &data, false)
+    .await
+}
+
+pub async fn set_token_mint(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+  token_mint: &TokenMint,
+) {
+  try_set_token_mint(context, address, token_mint)
+    .await.unwrap();
+}
+
+/// Writes an SPL Token mint in any state, i.e. with an arbitrary supply or authorities.
+pub async fn try_set_token_mint(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+  token_mint: &TokenMint,
+) -> FrameworkResult<()> {
+  let mut data = vec![0u8; TokenMint::LEN];
+  TokenMint::pack(*token_mint, &mut data)?;
+  try_set_account_with_data(context, address, &TOKEN_PROGRAM_ID, &data, false)
+    .await
+}
+
+pub async fn simulate_transaction(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+) -> TransactionOutcome {
+  try_simulate_transaction(context, payer, instructions, signers)
+    .await.unwrap()
+}
+
+/// Simulates the transaction without committing it to the bank.
+pub async fn try_simulate_transaction(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+) -> FrameworkResult<TransactionOutcome> {
+  try_simulate_transaction_with_options(
+    context,
+    payer,
+    instructions,
+    signers,
+    &TransactionOptions::default(),
+  ).await
+}
+
+pub async fn simulate_transaction_with_options(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+  options: &TransactionOptions,
+) -> TransactionOutcome {
+  try_simulate_transaction_with_options(context, payer, instructions, signers, options)
+    .await.unwrap()
+}
+
+pub async fn try_simulate_transaction_with_options(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+  options: &TransactionOptions,
+) -> FrameworkResult<TransactionOutcome> {
+  let tx = try_sign_transaction(context, payer, instructions, signers, options)
+    .await?;
+  let mut outcome = try_simulate_signed_transaction(context, tx)
+    .await?;
+  if should_refresh_blockhash(options, outcome.result.as_ref().err()) {
+    get_new_blockhash(context)
+      .await?;
+    let tx = try_sign_transaction(context, payer, instructions, signers, options)
+      .await?;
+    outcome = try_simulate_signed_transaction(context, tx)
+      .await?;
+  }
+  if let Err(error) = &mut outcome.result {
+    shift_instruction_error(error, options);
+  }
+  Ok(outcome)
+}
+
+pub async fn process_transaction2(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+  try_send_transaction(
+    context,
+    payer,
+    instructions,
+    signers,
+    &TransactionOptions::default(),
+  ).await
+}
+
+// HELPERS
+async fn try_simulate_signed_transaction(
+  context: &mut ProgramTestContext,
+  tx: Transaction,
+) -> FrameworkResult<TransactionOutcome> {
+  let txn_result = context.banks_client.simulate_transaction(tx)
+    .await?;
+  let result = txn_result.result
+    .unwrap_or(Err(TransactionError::SanitizeFailure));
+  let outcome = match txn_result.simulation_details {
+    Some(details) => TransactionOutcome {
+      result,
+      log_messages: details.logs,
+      compute_units_consumed: details.units_consumed,
+      return_data: details.return_data,
+    },
+    None => TransactionOutcome {
+      result,
+      log_messages: Vec::new(),
+      compute_units_consumed: 0,
+      return_data: None,
+    },
+  };
+  Ok(outcome)
+}
+
+/// Processes the transaction with preflight, sending it once more with a new blockhash if
+/// `options.blockhash_policy` asks for it.
+async fn try_send_transaction(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+  options: &TransactionOptions,
+) -> Result<(), BanksClientError> {
+  let tx = try_sign_transaction(context, payer, instructions, signers, options)
+    .await?;
+  let mut txn_result = try_process_signed_transaction(context, tx)
+    .await;
+  let txn_error = get_banks_client_transaction_error(&mut txn_result)
+    .as_deref()
+    .cloned();
+  if should_refresh_blockhash(options, txn_error.as_ref()) {
+    get_new_blockhash(context)
+      .await?;
+    let tx = try_sign_transaction(context, payer, instructions, signers, options)
+      .await?;
+    txn_result = try_process_signed_transaction(context, tx)
+      .await;
+  }
+  if let Some(error) = get_banks_client_transaction_error(&mut txn_result) {
+    shift_instruction_error(error, options);
+  }
+  txn_result
+}
+
+/// Processes a signed transaction, rejecting it with `TransactionError::AlreadyProcessed` if
+/// the bank already processed one with the same signature.
+async fn try_process_signed_transaction(
+  context: &mut ProgramTestContext,
+  tx: Transaction,
+) -> Result<(), BanksClientError> {
+  if is_transaction_processed(context, &tx).await? {
+    return Err(TransactionError::AlreadyProcessed.into());
+  }
+  context.banks_client.process_transaction(tx)
+    .await
+}
+
+/// Checks the status cache for the signature of the transaction, the banks server answers a
+/// duplicate with the status of the first transaction instead of rejecting it.
+async fn is_transaction_processed(
+  context: &mut ProgramTestContext,
+  tx: &Transaction,
+) -> Result<bool, BanksClientError> {
+  let status = context.banks_client.get_transaction_status(tx.signatures[0])
+    .await?;
+  Ok(status.is_some())
+}
+
+/// Rejects a transaction using a durable nonce before it reaches the banks server, which
+/// panics on a blockhash missing from its queue as is the case for every durable nonce.
+async fn try_check_durable_nonce(
+  context: &mut ProgramTestContext,
+  tx: &Transaction,
+) -> FrameworkResult<Option<TransactionError>> {
+  let nonce_instruction = match uses_durable_nonce(tx) {
+    Some(nonce_instruction) => nonce_instruction,
+    None => return Ok(None),
+  };
+  if tx.message.recent_blockhash == context.last_blockhash {
+    return Ok(None);
+  }
+  let nonce_account = nonce_instruction.accounts.first()
+    .and_then(|index| tx.message.account_keys.get(*index as usize));
+  let nonce = match nonce_account {
+    Some(nonce_account) => try_get_nonce(context, nonce_account).await.ok(),
+    None => None,
+  };
+  if nonce != Some(tx.message.recent_blockhash) {
+    return Ok(Some(TransactionError::BlockhashNotFound));
+  }
+  Err(FrameworkError::BanksClient(BanksClientError::ClientError(
+    "the banks server cannot process durable nonce transactions",
+  )))
+}
+
+/// Signs the transaction with `context.last_blockhash`, fetching a new one first under
+/// `BlockhashPolicy::AlwaysRefresh`, and prepends the `ComputeBudget` instructions.
+async fn try_sign_transaction(
+  context: &mut ProgramTestContext,
+  payer: &Keypair,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+  options: &TransactionOptions,
+) -> Result<Transaction, BanksClientError> {
+  if options.blockhash_policy == BlockhashPolicy::AlwaysRefresh {
+    get_new_blockhash(context)
+      .await?;
+  }
+  let mut budget_instructions = options.compute_budget.instructions();
+  budget_instructions.extend_from_slice(instructions);
+  let instructions = budget_instructions;
+  Ok(Transaction::new_signed_with_payer(
+    &instructions,
+    Some(&payer.pubkey()),
+    &signers.to_vec(),
+    context.last_blockhash,
+  ))
+}
+
+/// Checks if a transaction rejected with `error` is to be signed with a new blockhash and
+/// sent once more.
+fn should_refresh_blockhash(
+  options: &TransactionOptions,
+  error: Option<&TransactionError>,
+) -> bool {
+  match (options.blockhash_policy, error) {
+    (BlockhashPolicy::Reuse, _) | (BlockhashPolicy::AlwaysRefresh, _) => false,
+    (_, Some(TransactionError::BlockhashNotFound)) => true,
+    (BlockhashPolicy::RefreshWhenNeeded, Some(TransactionError::AlreadyProcessed)) => true,
+    _ => false,
+  }
+}
+
+fn get_banks_client_transaction_error(
+  txn_result: &mut Result<(), BanksClientError>,
+) -> Option<&mut TransactionError> {
+  match txn_result {
+    Err(BanksClientError::TransactionError(err)) => Some(err),
+    Err(BanksClientError::SimulationError { err, .. }) => Some(err),
+    _ => None,
+  }
+}
+
+/// Moves an instruction error past the prepended compute budget instructions back to the
+/// index of the instruction in the caller's list.
+fn shift_instruction_error(
+  error: &mut TransactionError,
+  options: &TransactionOptions,
+) {
+  let budget_instruction_count = options.compute_budget.instructions().len() as u8;
+  if let TransactionError::InstructionError(instruction_index, _) = error {
+    if *instruction_index >= budget_instruction_count {
+      *instruction_index -= budget_instruction_count;
+    }
+  }
+}
+
+async fn get_new_blockhash(
+  context: &mut ProgramTestContext,
+) -> io::Result<Hash> {
+  let blockhash = context.banks_client.get_new_latest_blockhash(&context.last_blockhash)
+    .await?;
+  context.last_blockhash = blockhash;
+  Ok(blockhash)
+}
+
+async fn get_account_with_owner(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+  expected_owner: &Pubkey,
+) -> FrameworkResult<Option<Account>> {
+  let account = match context.banks_client.get_account(*address).await? {
+    Some(account) => account,
+    None => return Ok(None),
+  };
+  if account.owner != *expected_owner {
+    return Err(FrameworkError::InvalidAccountOwner {
+      address: *address,
+      expected: *expected_owner,
+      actual: account.owner,
+    });
+  }
+  Ok(Some(account))
+}
+
+fn check_anchor_error<C: Into<u32> + Debug>(
+  error: Option<TransactionError>,
+  log_messages: &[String],
+  instruction_index: u8,
+  expected_error: C,
+) {
+  let expected_name = format!("{:?}", expected_error);
+  let expected_code: u32 = expected_error.into();
+  let expected = TransactionError::InstructionError(
+    instruction_index,
+    InstructionError::Custom(expected_code),
+  );
+  if error.as_ref() == Some(&expected) {
+    return;
+  }
+  let logged = match find_anchor_error(log_messages) {
+    Some(anchor_error) => format!(
+      "\n    logged: {} ({}): {}{}",
+      anchor_error.error_code,
+      anchor_error.error_number,
+      anchor_error.error_message,
+      anchor_error.error_origin
+        .map(|origin| format!(" at {}", origin))
+        .unwrap_or_default(),
+    ),
+    None => String::new(),
+  };
+  panic!(
+    "anchor error mismatch at instruction {}\n  expected: {} ({})\n    actual: {:?}{}",
+    instruction_index,
+    expected_name,
+    expected_code,
+    error,
+    logged,
+  );
+}
@@ -0,0 +1,142 @@
+use std::{
+  error,
+  fmt,
+  io,
+};
+use solana_program_test::{
+  BanksClientError,
+  ProgramTestError,
+};
+use solana_sdk::{
+  program_error::{
+    ProgramError,
+  },
+  pubkey::{
+    Pubkey,
+  },
+  transaction::{
+    TransactionError,
+  },
+};
+use super::{
+  benchmark::{
+    BASELINE_MODE_VARIABLE,
+  },
+};
+
+pub type FrameworkResult<T> = Result<T, FrameworkError>;
+
+/// Error returned by the `try_*` helpers of the framework.
+#[derive(Debug)]
+pub enum FrameworkError {
+  /// The banks client failed to process a request or the transaction failed.
+  BanksClient(BanksClientError),
+  /// Account data could not be decoded.
+  Program(ProgramError),
+  /// `ProgramTestContext` rejected the request, i.e. warping to a past slot.
+  ProgramTest(ProgramTestError),
+  /// No account exists at the given address.
+  AccountNotFound(Pubkey),
+  /// Reading or writing a file failed.
+  Io(io::Error),
+  /// An account fixture is malformed.
+  InvalidFixture(String),
+  /// The account is not owned by the expected program.
+  InvalidAccountOwner {
+    address: Pubkey,
+    expected: Pubkey,
+    actual: Pubkey,
+  },
+  /// The account data could not be deserialized into the requested type, i.e. because of a
+  /// discriminator mismatch.
+  InvalidAccountData {
+    address: Pubkey,
+    reason: String,
+  },
+  /// A compute unit baseline file is malformed.
+  InvalidBaseline(String),
+  /// A benchmark scenario has no compute unit baseline.
+  MissingBaseline(String),
+  /// A benchmark scenario consumed more compute units than its baseline allows.
+  ComputeUnitsExceeded {
+    scenario: String,
+    baseline: u64,
+    compute_units: u64,
+  },
+}
+
+impl FrameworkError {
+  /// Returns the `TransactionError` if the transaction was processed and failed.
+  pub fn transaction_error(&self) -> Option<TransactionError> {
+    match self {
+      FrameworkError::BanksClient(BanksClientError::TransactionError(err)) => Some(err.clone()),
+      FrameworkError::BanksClient(BanksClientError::SimulationError { err, .. }) => Some(err.clone()),
+      _ => None,
+    }
+  }
+  /// Returns the log messages captured during preflight simulation, if any.
+  pub fn log_messages(&self) -> Option<&[String]> {
+    match self {
+      FrameworkError::BanksClient(BanksClientError::SimulationError { logs, .. }) => Some(logs),
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for FrameworkError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      FrameworkError::BanksClient(err) => write!(f, "{}", err),
+      FrameworkError::Program(err) => write!(f, "{}", err),
+      FrameworkError::ProgramTest(err) => write!(f, "{}", err),
+      FrameworkError::AccountNotFound(address) => write!(f, "account {} not found", address),
+      FrameworkError::Io(err) => write!(f, "{}", err),
+      FrameworkError::InvalidFixture(reason) => write!(f, "invalid account fixture: {}", reason),
+      FrameworkError::InvalidAccountOwner { address, expected, actual } => {
+        write!(f, "account {} is owned by {}, expected {}", address, actual, expected)
+      },
+      FrameworkError::InvalidAccountData { address, reason } => {
+        write!(f, "invalid data in account {}: {}", address, reason)
+      },
+      FrameworkError::InvalidBaseline(reason) => write!(f, "invalid compute unit baseline: {}", reason),
+      FrameworkError::MissingBaseline(scenario) => {
+        write!(f, "no compute unit baseline for {}, run with {}=update to record it", scenario, BASELINE_MODE_VARIABLE)
+      },
+      FrameworkError::ComputeUnitsExceeded { scenario, baseline, compute_units } => {
+        write!(f, "{} consumed {} compute units, baseline is {}", scenario, compute_units, baseline)
+      },
+    }
+  }
+}
+
+impl error::Error for FrameworkError {}
+
+impl From<BanksClientError> for FrameworkError {
+  fn from(err: BanksClientError) -> Self {
+    FrameworkError::BanksClient(err)
+  }
+}
+
+impl From<TransactionError> for FrameworkError {
+  fn from(err: TransactionError) -> Self {
+    FrameworkError::BanksClient(BanksClientError::TransactionError(err))
+  }
+}
+
+impl From<ProgramError> for FrameworkError {
+  fn from(err: ProgramError) -> Self {
+    FrameworkError::Program(err)
+  }
+}
+
+impl From<ProgramTestError> for FrameworkError {
+  fn from(err: ProgramTestError) -> Self {
+    FrameworkError::ProgramTest(err)
+  }
+}
+
+impl From<io::Error> for FrameworkError {
+  fn from(err: io::Error) -> Self {
+    FrameworkError::Io(err)
+  }
+}
@@ -0,0 +1,150 @@
+use std::{
+  fs,
+  path::{
+    Path,
+  },
+};
+use serde::{
+  Deserialize,
+  Serialize,
+};
+use solana_program_test::{
+  ProgramTestContext,
+};
+use solana_sdk::{
+  account::{
+    Account,
+  },
+  pubkey::{
+    Pubkey,
+  },
+};
+use super::{
+  error::{
+    FrameworkError,
+    FrameworkResult,
+  },
+};
+
+const BASE64_ENCODING: &str = "base64";
+const FIXTURE_EXTENSION: &str = "json";
+
+/// Account dump in the format of `solana account <ADDRESS> --output json`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AccountFixture {
+  pub pubkey: String,
+  pub account: AccountFixtureData,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountFixtureData {
+  pub lamports: u64,
+  /// Encoded data followed by its encoding, i.e. `["AQAAAA==", "base64"]`.
+  pub data: (String, String),
+  pub owner: String,
+  pub executable: bool,
+  pub rent_epoch: u64,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub space: Option<u64>,
+}
+
+impl AccountFixture {
+  pub fn new(
+    address: &Pubkey,
+    account: &Account,
+  ) -> AccountFixture {
+    AccountFixture {
+      pubkey: address.to_string(),
+      account: AccountFixtureData {
+        lamports: account.lamports,
+        data: (base64::encode(&account.data), BASE64_ENCODING.to_string()),
+        owner: account.owner.to_string(),
+        executable: account.executable,
+        rent_epoch: account.rent_epoch,
+        space: Some(account.data.len() as u64),
+      },
+    }
+  }
+
+  pub fn to_account(&self) -> FrameworkResult<(Pubkey, Account)> {
+    let address = parse_pubkey(&self.pubkey)?;
+    let owner = parse_pubkey(&self.account.owner)?;
+    let (encoded_data, encoding) = &self.account.data;
+    if encoding != BASE64_ENCODING {
+      return Err(FrameworkError::InvalidFixture(format!("unsupported data encoding {}", encoding)));
+    }
+    let data = base64::decode(encoded_data)
+      .map_err(|err| FrameworkError::InvalidFixture(err.to_string()))?;
+    let account = Account {
+      lamports: self.account.lamports,
+      data,
+      owner,
+      executable: self.account.executable,
+      rent_epoch: self.account.rent_epoch,
+    };
+    Ok((address, account))
+  }
+}
+
+pub fn read_account_fixture(
+  path: &Path,
+) -> FrameworkResult<(Pubkey, Account)> {
+  let content = fs::read_to_string(path)?;
+  let fixture: AccountFixture = serde_json::from_str(&content)
+    .map_err(|err| FrameworkError::InvalidFixture(format!("{}: {}", path.display(), err)))?;
+  fixture.to_account()
+}
+
+/// Reads every `*.json` fixture of a directory, sorted by file name.
+pub fn read_account_fixtures(
+  directory: &Path,
+) -> FrameworkResult<Vec<(Pubkey, Account)>> {
+  let mut paths = Vec::new();
+  for entry in fs::read_dir(directory)? {
+    let path = entry?.path();
+    if path.is_file() && path.extension().map_or(false, |extension| extension == FIXTURE_EXTENSION) {
+      paths.push(path);
+    }
+  }
+  paths.sort();
+  paths.iter()
+    .map(|path| read_account_fixture(path))
+    .collect()
+}
+
+pub fn write_account_fixture(
+  path: &Path,
+  address: &Pubkey,
+  account: &Account,
+) -> FrameworkResult<()> {
+  let fixture = AccountFixture::new(address, account);
+  let content = serde_json::to_string_pretty(&fixture)
+    .map_err(|err| FrameworkError::InvalidFixture(err.to_string()))?;
+  fs::write(path, content + "\n")?;
+  Ok(())
+}
+
+/// Writes the current state of `addresses` into `<directory>/<address>.json`.
+pub async fn export_account_fixtures(
+  context: &mut ProgramTestContext,
+  addresses: &[Pubkey],
+  directory: &Path,
+) -> FrameworkResult<()> {
+  fs::create_dir_all(directory)?;
+  for address in addresses {
+    let account = context.banks_client
+      .get_account(*address)
+      .await?
+      .ok_or(FrameworkError::AccountNotFound(*address))?;
+    let path = directory.join(format!("{}.{}", address, FIXTURE_EXTENSION));
+    write_account_fixture(&path, address, &account)?;
+  }
+  Ok(())
+}
+
+// HELPERS
+fn parse_pubkey(value: &str) -> FrameworkResult<Pubkey> {
+  value.parse::<Pubkey>()
+    .map_err(|_| FrameworkError::InvalidFixture(format!("invalid pubkey {}", value)))
+}
@@ -0,0 +1,12 @@
+pub mod account;
+pub mod benchmark;
+pub mod context;
+pub mod error;
+pub mod fixture;
+pub mod log_message;
+pub mod merkle_tree;
+pub mod spl_atoken;
+pub mod spl_token;
+pub mod spl_token_2022;
+pub mod system;
+pub mod token;
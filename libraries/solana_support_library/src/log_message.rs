@@ -0,0 +1,264 @@
+use anchor_lang::{
+  Event,
+};
+use solana_sdk::{
+  pubkey::{
+    Pubkey,
+  },
+};
+
+const ANCHOR_PROGRAM_ERROR: &str = "Program log: AnchorError ";
+const ANCHOR_ERROR_CODE: &str = ". Error Code: ";
+const ANCHOR_ERROR_MESSAGE: &str = ". Error Message: ";
+const ANCHOR_ERROR_NUMBER: &str = ". Error Number: ";
+const PROGRAM_DATA: &str = "Program data: ";
+const PROGRAM_ERROR: &str = "Program log: Error: ";
+const PROGRAM_LOG: &str = "Program log: ";
+const PROGRAM_PANIC: &str = "Program log: panicked at";
+const PROGRAM_RETURN: &str = "Program return: ";
+
+/// A program invocation reconstructed from log messages, CPI calls are nested in `children`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProgramInvocation {
+  /// The invoked program.
+  pub program_id: Pubkey,
+  /// Invocation depth as reported by `invoke [n]`, 1 for top-level instructions.
+  pub depth: usize,
+  /// Content of `Program log:` lines, without the prefix.
+  pub messages: Vec<String>,
+  /// Base64 payloads of `Program data:` lines, without the prefix.
+  pub data: Vec<String>,
+  /// Base64 payload of the `Program return:` line, if any.
+  pub return_data: Option<String>,
+  /// Compute units consumed, including the units consumed by children.
+  /// Builtin programs do not report their usage so this is `None` for them.
+  pub compute_units_consumed: Option<u64>,
+  /// Compute units that were available to the invocation when it started.
+  pub compute_units_limit: Option<u64>,
+  /// Is `true` if the program logged `success`
+  pub is_success: bool,
+  /// Reason logged after `failed:`, i.e. `custom program error: 0x1770`.
+  pub failure_reason: Option<String>,
+  /// Error logged by the program itself, i.e. an `AnchorError` or a panic message.
+  pub error_message: Option<String>,
+  /// Invocations made by this program through CPI.
+  pub children: Vec<ProgramInvocation>,
+}
+
+impl ProgramInvocation {
+  /// Returns this invocation followed by all of its descendants, depth-first.
+  pub fn flatten(&self) -> Vec<&ProgramInvocation> {
+    let mut results = vec![self];
+    for child in &self.children {
+      results.extend(child.flatten());
+    }
+    results
+  }
+}
+
+/// Error logged by an Anchor program when an instruction fails.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnchorErrorLog {
+  /// Name of the error variant, i.e. `ContentTooLong`.
+  pub error_code: String,
+  /// Error number including the Anchor offset, i.e. `6000`.
+  pub error_number: u32,
+  /// Message declared with `#[msg]`.
+  pub error_message: String,
+  /// Source location `file:line` or account name the error was raised for, if logged.
+  pub error_origin: Option<String>,
+}
+
+/// Events of type `T` emitted by a single program invocation.
+#[derive(Clone, Debug)]
+pub struct InvocationEvents<T> {
+  /// The invoked program.
+  pub program_id: Pubkey,
+  /// Invocation depth as reported by `invoke [n]`, 1 for top-level instructions.
+  pub depth: usize,
+  /// Events in the order they were emitted.
+  pub events: Vec<T>,
+}
+
+/// Parses log messages into one `ProgramInvocation` per top-level instruction.
+pub fn parse_log_messages(
+  log_messages: &[String],
+) -> Vec<ProgramInvocation> {
+  let mut results: Vec<ProgramInvocation> = Vec::new();
+  let mut processing_results: Vec<ProgramInvocation> = Vec::new();
+  for message in log_messages {
+    if let Some((program_id, depth)) = parse_invoke_log(message) {
+      processing_results.push(ProgramInvocation {
+        program_id,
+        depth,
+        ..ProgramInvocation::default()
+      });
+      continue;
+    }
+    let current_result = match processing_results.last_mut() {
+      Some(result) => result,
+      None => continue,
+    };
+    if let Some((_, consumed, limit)) = parse_consumed_log(message) {
+      current_result.compute_units_consumed = Some(consumed);
+      current_result.compute_units_limit = Some(limit);
+      continue;
+    }
+    if let Some((_, status)) = parse_result_log(message) {
+      let mut finished_result = processing_results.pop().unwrap();
+      match status {
+        Ok(()) => finished_result.is_success = true,
+        Err(reason) => finished_result.failure_reason = Some(reason),
+      }
+      match processing_results.last_mut() {
+        Some(parent_result) => parent_result.children.push(finished_result),
+        None => results.push(finished_result),
+      }
+      continue;
+    }
+    if let Some(content) = message.strip_prefix(PROGRAM_DATA) {
+      current_result.data.extend(content.split_whitespace().map(String::from));
+      continue;
+    }
+    if let Some(content) = message.strip_prefix(PROGRAM_RETURN) {
+      current_result.return_data = content.split_once(' ')
+        .map(|(_, data)| data.to_string());
+      continue;
+    }
+    if let Some(error_message) = message.strip_prefix(PROGRAM_ERROR) {
+      current_result.error_message = Some(error_message.to_string());
+    }
+    else if message.starts_with(ANCHOR_PROGRAM_ERROR) || message.starts_with(PROGRAM_PANIC) {
+      current_result.error_message = Some(message[PROGRAM_LOG.len()..].to_string());
+    }
+    if let Some(content) = message.strip_prefix(PROGRAM_LOG) {
+      current_result.messages.push(content.to_string());
+    }
+  }
+  // Invocations interrupted by the end of the logs, i.e. truncated or exceeded compute budget
+  while let Some(unfinished_result) = processing_results.pop() {
+    match processing_results.last_mut() {
+      Some(parent_result) => parent_result.children.push(unfinished_result),
+      None => results.push(unfinished_result),
+    }
+  }
+  results
+}
+
+/// Parses a line such as `Program log: AnchorError thrown in src/lib.rs:28. Error Code: ContentTooLong.
+/// Error Number: 6000. Error Message: Content is too long.`
+pub fn parse_anchor_error_log(
+  message: &str,
+) -> Option<AnchorErrorLog> {
+  let content = message.strip_prefix(ANCHOR_PROGRAM_ERROR)?;
+  let (origin, content) = content.split_once(ANCHOR_ERROR_CODE)?;
+  let (error_code, content) = content.split_once(ANCHOR_ERROR_NUMBER)?;
+  let (error_number, error_message) = content.split_once(ANCHOR_ERROR_MESSAGE)?;
+  let error_origin = origin.strip_prefix("thrown in ")
+    .or_else(|| origin.strip_prefix("caused by account: "))
+    .map(String::from);
+  Some(AnchorErrorLog {
+    error_code: error_code.to_string(),
+    error_number: error_number.parse::<u32>().ok()?,
+    error_message: error_message.strip_suffix('.').unwrap_or(error_message).to_string(),
+    error_origin,
+  })
+}
+
+/// Returns the last `AnchorError` logged by the transaction.
+pub fn find_anchor_error(
+  log_messages: &[String],
+) -> Option<AnchorErrorLog> {
+  log_messages.iter()
+    .rev()
+    .find_map(|message| parse_anchor_error_log(message))
+}
+
+/// Returns the innermost failed invocation, following the failure down the CPI chain.
+pub fn find_failed_invocation(
+  invocations: &[ProgramInvocation],
+) -> Option<&ProgramInvocation> {
+  let failed_invocation = invocations.iter()
+    .find(|invocation| !invocation.is_success)?;
+  match find_failed_invocation(&failed_invocation.children) {
+    Some(inner_invocation) => Some(inner_invocation),
+    None => Some(failed_invocation),
+  }
+}
+
+/// Decodes an Anchor event from `discriminator || borsh(event)`, returns `None`
+/// if the discriminator does not belong to `T`.
+pub fn decode_event<T: Event>(
+  data: &[u8],
+) -> Option<T> {
+  if data.len() < 8 || data[..8] != T::discriminator() {
+    return None;
+  }
+  T::try_from_slice(&data[8..]).ok()
+}
+
+/// Scans `Program data:` lines and decodes every event of type `T`, grouped by
+/// the program invocation that emitted it. Invocations are listed in the order
+/// they started, including those which emitted no event of type `T`.
+pub fn decode_events<T: Event>(
+  log_messages: &[String],
+) -> Vec<InvocationEvents<T>> {
+  let invocations = parse_log_messages(log_messages);
+  invocations.iter()
+    .flat_map(|invocation| invocation.flatten())
+    .map(|invocation| InvocationEvents {
+      program_id: invocation.program_id,
+      depth: invocation.depth,
+      events: invocation.data.iter()
+        .filter_map(|encoded_data| base64::decode(encoded_data).ok())
+        .filter_map(|data| decode_event::<T>(&data))
+        .collect(),
+    })
+    .collect()
+}
+
+/// Returns all events of type `T` emitted by the transaction regardless of the emitter.
+pub fn get_events<T: Event>(
+  log_messages: &[String],
+) -> Vec<T> {
+  decode_events::<T>(log_messages)
+    .into_iter()
+    .flat_map(|invocation| invocation.events)
+    .collect()
+}
+
+// HELPERS
+fn parse_invoke_log(message: &str) -> Option<(Pubkey, usize)> {
+  let parts: Vec<&str> = message.split(' ').collect();
+  if parts.len() != 4 || parts[0] != "Program" || parts[2] != "invoke" {
+    return None;
+  }
+  let program_id = parts[1].parse::<Pubkey>().ok()?;
+  let depth = parts[3].strip_prefix('[')?
+    .strip_suffix(']')?
+    .parse::<usize>().ok()?;
+  Some((program_id, depth))
+}
+
+fn parse_consumed_log(message: &str) -> Option<(Pubkey, u64, u64)> {
+  // Program <id> consumed <consumed> of <limit> compute units
+  let parts: Vec<&str> = message.split(' ').collect();
+  if parts.len() != 8 || parts[0] != "Program" || parts[2] != "consumed" || parts[4] != "of" {
+    return None;
+  }
+  let program_id = parts[1].parse::<Pubkey>().ok()?;
+  let consumed = parts[3].parse::<u64>().ok()?;
+  let limit = parts[5].parse::<u64>().ok()?;
+  Some((program_id, consumed, limit))
+}
+
+fn parse_result_log(message: &str) -> Option<(Pubkey, Result<(), String>)> {
+  let content = message.strip_prefix("Program ")?;
+  let (program_id, status) = content.split_once(' ')?;
+  let program_id = program_id.parse::<Pubkey>().ok()?;
+  if status == "success" {
+    return Some((program_id, Ok(())));
+  }
+  let reason = status.strip_prefix("failed: ")?;
+  Some((program_id, Err(reason.to_string())))
+}
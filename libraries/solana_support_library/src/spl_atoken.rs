@@ -0,0 +1,229 @@
+use solana_sdk::{
+  instruction::{
+    AccountMeta,
+    Instruction,
+  },
+  pubkey::{
+    Pubkey,
+  },
+  system_program::{
+    ID as SYSTEM_PROGRAM_ID,
+  },
+  sysvar::{
+    rent::{
+      ID as SYSVAR_RENT_ID,
+    },
+  },
+};
+use super::{
+  spl_token::{
+    ID as TOKEN_PROGRAM_ID,
+  },
+};
+
+// ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL
+pub const ID: Pubkey = Pubkey::new_from_array([140,151,37,143,78,36,137,241,187,61,16,41,20,142,13,131,11,90,19,153,218,255,16,132,4,142,123,216,219,233,248,89]);
+
+/// Instructions of the associated token account program.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AssociatedTokenAccountInstruction {
+  /// Creates an associated token account, fails if it already exists
+  Create,
+  /// Creates an associated token account if it does not exist yet
+  CreateIdempotent,
+  /// Moves tokens out of an associated token account owned by another associated token
+  /// account of the same wallet, then closes it
+  RecoverNested,
+}
+
+pub fn create_associated_token_account_instruction(
+  payer_address: &Pubkey,
+  wallet_address: &Pubkey,
+  token_mint_address: &Pubkey,
+) -> Instruction {
+  create_associated_token_account_with_program_id_instruction(
+    payer_address,
+    wallet_address,
+    token_mint_address,
+    &TOKEN_PROGRAM_ID,
+  )
+}
+
+pub fn create_associated_token_account_with_program_id_instruction(
+  payer_address: &Pubkey,
+  wallet_address: &Pubkey,
+  token_mint_address: &Pubkey,
+  token_program_id: &Pubkey,
+) -> Instruction {
+  build_create_instruction(
+    AssociatedTokenAccountInstruction::Create,
+    payer_address,
+    wallet_address,
+    token_mint_address,
+    token_program_id,
+  )
+}
+
+/// Same as `create_associated_token_account_instruction` but succeeds if the account
+/// already exists with the expected owner.
+pub fn create_associated_token_account_idempotent_instruction(
+  payer_address: &Pubkey,
+  wallet_address: &Pubkey,
+  token_mint_address: &Pubkey,
+) -> Instruction {
+  create_associated_token_account_idempotent_with_program_id_instruction(
+    payer_address,
+    wallet_address,
+    token_mint_address,
+    &TOKEN_PROGRAM_ID,
+  )
+}
+
+pub fn create_associated_token_account_idempotent_with_program_id_instruction(
+  payer_address: &Pubkey,
+  wallet_address: &Pubkey,
+  token_mint_address: &Pubkey,
+  token_program_id: &Pubkey,
+) -> Instruction {
+  build_create_instruction(
+    AssociatedTokenAccountInstruction::CreateIdempotent,
+    payer_address,
+    wallet_address,
+    token_mint_address,
+    token_program_id,
+  )
+}
+
+pub fn find_associated_token_account_address(
+  wallet_address: &Pubkey,
+  token_mint_address: &Pubkey,
+) -> (Pubkey, u8) {
+  find_associated_token_account_address_with_program_id(
+    wallet_address,
+    token_mint_address,
+    &TOKEN_PROGRAM_ID,
+  )
+}
+
+pub fn find_associated_token_account_address_with_program_id(
+  wallet_address: &Pubkey,
+  token_mint_address: &Pubkey,
+  token_program_id: &Pubkey,
+) -> (Pubkey, u8) {
+  Pubkey::find_program_address(
+    &[
+      &wallet_address.to_bytes(),
+      &token_program_id.to_bytes(),
+      &token_mint_address.to_bytes(),
+    ],
+    &ID,
+  )
+}
+
+pub fn get_associated_token_account_address(
+  wallet_address: &Pubkey,
+  token_mint_address: &Pubkey,
+) -> Pubkey {
+  let (addr, _) = find_associated_token_account_address(wallet_address, token_mint_address);
+  addr
+}
+
+pub fn get_associated_token_account_address_with_program_id(
+  wallet_address: &Pubkey,
+  token_mint_address: &Pubkey,
+  token_program_id: &Pubkey,
+) -> Pubkey {
+  let (addr, _) = find_associated_token_account_address_with_program_id(wallet_address, token_mint_address, token_program_id);
+  addr
+}
+
+pub fn recover_nested_associated_token_account_instruction(
+  wallet_address: &Pubkey,
+  owner_token_mint_address: &Pubkey,
+  nested_token_mint_address: &Pubkey,
+) -> Instruction {
+  recover_nested_associated_token_account_with_program_id_instruction(
+    wallet_address,
+    owner_token_mint_address,
+    nested_token_mint_address,
+    &TOKEN_PROGRAM_ID,
+  )
+}
+
+/// Recovers the `nested_token_mint` associated token account owned by the
+/// `owner_token_mint` associated token account of `wallet`, into the `nested_token_mint`
+/// associated token account of `wallet`.
+pub fn recover_nested_associated_token_account_with_program_id_instruction(
+  wallet_address: &Pubkey,
+  owner_token_mint_address: &Pubkey,
+  nested_token_mint_address: &Pubkey,
+  token_program_id: &Pubkey,
+) -> Instruction {
+
+  let data: Vec<u8> = vec![AssociatedTokenAccountInstruction::RecoverNested as u8];
+
+  let owner_associated_account_address = get_associated_token_account_address_with_program_id(
+    wallet_address,
+    owner_token_mint_address,
+    token_program_id,
+  );
+  let destination_associated_account_address = get_associated_token_account_address_with_program_id(
+    wallet_address,
+    nested_token_mint_address,
+    token_program_id,
+  );
+  let nested_associated_account_address = get_associated_token_account_address_with_program_id(
+    &owner_associated_account_address,
+    nested_token_mint_address,
+    token_program_id,
+  );
+  let accounts = vec![
+    AccountMeta::new(nested_associated_account_address, false),
+    AccountMeta::new_readonly(*nested_token_mint_address, false),
+    AccountMeta::new(destination_associated_account_address, false),
+    AccountMeta::new_readonly(owner_associated_account_address, false),
+    AccountMeta::new_readonly(*owner_token_mint_address, false),
+    AccountMeta::new(*wallet_address, true),
+    AccountMeta::new_readonly(*token_program_id, false),
+  ];
+
+  Instruction {
+    data,
+    accounts,
+    program_id: ID,
+  }
+}
+
+// HELPERS
+fn build_create_instruction(
+  instruction: AssociatedTokenAccountInstruction,
+  payer_address: &Pubkey,
+  wallet_address: &Pubkey,
+  token_mint_address: &Pubkey,
+  token_program_id: &Pubkey,
+) -> Instruction {
+
+  let data: Vec<u8> = vec![instruction as u8];
+
+  let (associated_account_address, _) = find_associated_token_account_address_with_program_id(
+    wallet_address,
+    token_mint_address,
+    token_program_id,
+  );
+  let accounts = vec![
+    AccountMeta::new(*payer_address, true),
+    AccountMeta::new(associated_account_address, false),
+    AccountMeta::new_readonly(*wallet_address, false),
+    AccountMeta::new_readonly(*token_mint_address, false),
+    AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    AccountMeta::new_readonly(*token_program_id, false),
+    AccountMeta::new_readonly(SYSVAR_RENT_ID, false),
+  ];
+
+  Instruction {
+    data,
+    accounts,
+    program_id: ID,
+  }
+}
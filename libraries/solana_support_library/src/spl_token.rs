@@ -0,0 +1,1496 @@
+use std::{
+  mem::{
+    size_of,
+  },
+};
+use arrayref::{
+  array_mut_ref,
+  array_ref,
+  array_refs,
+  mut_array_refs,
+};
+use num_enum::{
+  TryFromPrimitive,
+};
+use solana_sdk::{
+  instruction::{
+    AccountMeta,
+    Instruction,
+  },
+  program_error::{
+    ProgramError,
+  },
+  program_option::{
+    COption,
+  },
+  program_pack::{
+    IsInitialized,
+    Pack,
+    Sealed,
+  },
+  pubkey::{
+    Pubkey,
+  },
+  sysvar::{
+    rent::{
+      ID as RENT_SYSVAR_ID,
+    }
+  }
+};
+use super::{
+  spl_token_2022::{
+    ID as TOKEN_2022_PROGRAM_ID,
+  },
+};
+
+// TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA
+pub const ID: Pubkey = Pubkey::new_from_array([6,221,246,225,215,101,161,147,217,203,225,70,206,235,121,172,28,180,133,237,95,91,55,145,58,140,245,133,126,255,0,169]);
+
+// So11111111111111111111111111111111111111112
+pub const NATIVE_MINT_ID: Pubkey = Pubkey::new_from_array([6,155,136,87,254,171,129,132,251,104,127,99,70,24,192,53,218,196,57,220,26,235,59,85,152,160,240,0,0,0,0,1]);
+/// Decimals of the native mint, one token equals one lamport.
+pub const NATIVE_MINT_DECIMALS: u8 = 9;
+
+pub const TOKEN_ACCOUNT_LENGTH: usize = 165;
+pub const TOKEN_MINT_LENGTH: usize = 82;
+pub const TOKEN_MULTISIG_LENGTH: usize = 355;
+/// Maximum number of signers of a multisig account.
+pub const MAX_SIGNERS: usize = 11;
+
+/// Account data.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TokenAccount {
+  /// The mint associated with this account
+  pub mint: Pubkey,
+  /// The owner of this account.
+  pub owner: Pubkey,
+  /// The amount of tokens this account holds.
+  pub amount: u64,
+  /// If `delegate` is `Some` then `delegated_amount` represents
+  /// the amount authorized by the delegate
+  pub delegate: COption<Pubkey>,
+  /// The account's state
+  pub state: AccountState,
+  /// If is_native.is_some, this is a native token, and the value logs the rent-exempt reserve. An
+  /// Account is required to be rent-exempt, so the value is used by the Processor to ensure that
+  /// wrapped SOL accounts do not drop below this threshold.
+  pub is_native: COption<u64>,
+  /// The amount delegated
+  pub delegated_amount: u64,
+  /// Optional authority to close the account.
+  pub close_authority: COption<Pubkey>,
+}
+
+impl TokenAccount {
+  /// Checks if account is frozen
+  pub fn is_frozen(&self) -> bool {
+    self.state == AccountState::Frozen
+  }
+  /// Checks if account is native
+  pub fn is_native(&self) -> bool {
+    self.is_native.is_some()
+  }
+}
+impl Sealed for TokenAccount {}
+impl IsInitialized for TokenAccount {
+  fn is_initialized(&self) -> bool {
+    self.state != AccountState::Uninitialized
+  }
+}
+impl Pack for TokenAccount {
+  const LEN: usize = 165;
+  fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+    let src = array_ref![src, 0, 165];
+    let (mint, owner, amount, delegate, state, is_native, delegated_amount, close_authority) =
+      array_refs![src, 32, 32, 8, 36, 1, 12, 8, 36];
+    Ok(TokenAccount {
+      mint: Pubkey::new_from_array(*mint),
+      owner: Pubkey::new_from_array(*owner),
+      amount: u64::from_le_bytes(*amount),
+      delegate: unpack_coption_key(delegate)?,
+      state: AccountState::try_from_primitive(state[0])
+        .or(Err(ProgramError::InvalidAccountData))?,
+      is_native: unpack_coption_u64(is_native)?,
+      delegated_amount: u64::from_le_bytes(*delegated_amount),
+      close_authority: unpack_coption_key(close_authority)?,
+    })
+  }
+  fn pack_into_slice(&self, dst: &mut [u8]) {
+    let dst = array_mut_ref![dst, 0, 165];
+    let (
+      mint_dst,
+      owner_dst,
+      amount_dst,
+      delegate_dst,
+      state_dst,
+      is_native_dst,
+      delegated_amount_dst,
+      close_authority_dst,
+    ) = mut_array_refs![dst, 32, 32, 8, 36, 1, 12, 8, 36];
+    let &TokenAccount {
+      ref mint,
+      ref owner,
+      amount,
+      ref delegate,
+      state,
+      ref is_native,
+      delegated_amount,
+      ref close_authority,
+    } = self;
+    mint_dst.copy_from_slice(mint.as_ref());
+    owner_dst.copy_from_slice(owner.as_ref());
+    *amount_dst = amount.to_le_bytes();
+    pack_coption_key(delegate, delegate_dst);
+    state_dst[0] = state as u8;
+    pack_coption_u64(is_native, is_native_dst);
+    *delegated_amount_dst = delegated_amount.to_le_bytes();
+    pack_coption_key(close_authority, close_authority_dst);
+  }
+}
+
+/// Account state.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, TryFromPrimitive)]
+pub enum AccountState {
+  /// Account is not yet initialized
+  Uninitialized,
+  /// Account is initialized; the account owner and/or delegate may perform permitted operations
+  /// on this account
+  Initialized,
+  /// Account has been frozen by the mint freeze authority. Neither the account owner nor
+  /// the delegate are able to perform operations on this account.
+  Frozen,
+}
+
+impl Default for AccountState {
+  fn default() -> Self {
+    AccountState::Uninitialized
+  }
+}
+
+/// Mint data.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TokenMint {
+  /// Optional authority used to mint new tokens. The mint authority may only be provided during
+  /// mint creation. If no mint authority is present then the mint has a fixed supply and no
+  /// further tokens may be minted.
+  pub mint_authority: COption<Pubkey>,
+  /// Total supply of tokens.
+  pub supply: u64,
+  /// Number of base 10 digits to the right of the decimal place.
+  pub decimals: u8,
+  /// Is `true` if this structure has been initialized
+  pub is_initialized: bool,
+  /// Optional authority to freeze token accounts.
+  pub freeze_authority: COption<Pubkey>,
+}
+impl Sealed for TokenMint {}
+impl IsInitialized for TokenMint {
+  fn is_initialized(&self) -> bool {
+    self.is_initialized
+  }
+}
+impl Pack for TokenMint {
+  const LEN: usize = 82;
+  fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+    let src = array_ref![src, 0, 82];
+    let (mint_authority, supply, decimals, is_initialized, freeze_authority) =
+      array_refs![src, 36, 8, 1, 1, 36];
+    let mint_authority = unpack_coption_key(mint_authority)?;
+    let supply = u64::from_le_bytes(*supply);
+    let decimals = decimals[0];
+    let is_initialized = match is_initialized {
+      [0] => false,
+      [1] => true,
+      _ => return Err(ProgramError::InvalidAccountData),
+    };
+    let freeze_authority = unpack_coption_key(freeze_authority)?;
+    Ok(TokenMint {
+      mint_authority,
+      supply,
+      decimals,
+      is_initialized,
+      freeze_authority,
+    })
+  }
+  fn pack_into_slice(&self, dst: &mut [u8]) {
+    let dst = array_mut_ref![dst, 0, 82];
+    let (
+      mint_authority_dst,
+      supply_dst,
+      decimals_dst,
+      is_initialized_dst,
+      freeze_authority_dst,
+    ) = mut_array_refs![dst, 36, 8, 1, 1, 36];
+    let &TokenMint {
+      ref mint_authority,
+      supply,
+      decimals,
+      is_initialized,
+      ref freeze_authority,
+    } = self;
+    pack_coption_key(mint_authority, mint_authority_dst);
+    *supply_dst = supply.to_le_bytes();
+    decimals_dst[0] = decimals;
+    is_initialized_dst[0] = is_initialized as u8;
+    pack_coption_key(freeze_authority, freeze_authority_dst);
+  }
+}
+
+/// Multisignature data.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TokenMultisig {
+  /// Number of signers required
+  pub m: u8,
+  /// Number of valid signers
+  pub n: u8,
+  /// Is `true` if this structure has been initialized
+  pub is_initialized: bool,
+  /// Signer public keys
+  pub signers: [Pubkey; MAX_SIGNERS],
+}
+
+impl TokenMultisig {
+  /// Returns the valid signers, the first `n` entries of `signers`
+  pub fn valid_signers(&self) -> &[Pubkey] {
+    &self.signers[..self.n as usize]
+  }
+}
+impl Sealed for TokenMultisig {}
+impl IsInitialized for TokenMultisig {
+  fn is_initialized(&self) -> bool {
+    self.is_initialized
+  }
+}
+impl Pack for TokenMultisig {
+  const LEN: usize = 355;
+  fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+    let src = array_ref![src, 0, 355];
+    let (m, n, is_initialized, signers_flat) = array_refs![src, 1, 1, 1, 32 * MAX_SIGNERS];
+    let mut result = TokenMultisig {
+      m: m[0],
+      n: n[0],
+      is_initialized: match is_initialized {
+        [0] => false,
+        [1] => true,
+        _ => return Err(ProgramError::InvalidAccountData),
+      },
+      signers: [Pubkey::new_from_array([0u8; 32]); MAX_SIGNERS],
+    };
+    for (src, dst) in signers_flat.chunks(32).zip(result.signers.iter_mut()) {
+      *dst = Pubkey::try_from(src)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    }
+    Ok(result)
+  }
+  fn pack_into_slice(&self, dst: &mut [u8]) {
+    let dst = array_mut_ref![dst, 0, 355];
+    let (
+      m_dst,
+      n_dst,
+      is_initialized_dst,
+      signers_flat_dst,
+    ) = mut_array_refs![dst, 1, 1, 1, 32 * MAX_SIGNERS];
+    m_dst[0] = self.m;
+    n_dst[0] = self.n;
+    is_initialized_dst[0] = self.is_initialized as u8;
+    for (i, src) in self.signers.iter().enumerate() {
+      let dst_array = array_mut_ref![signers_flat_dst, 32 * i, 32];
+      dst_array.copy_from_slice(src.as_ref());
+    }
+  }
+}
+
+#[repr(u8)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum AuthorityType {
+  MintTokens,
+  FreezeAccount,
+  AccountOwner,
+  CloseAccount,
+}
+
+impl AuthorityType {
+  fn into(&self) -> u8 {
+    match self {
+      AuthorityType::MintTokens => 0,
+      AuthorityType::FreezeAccount => 1,
+      AuthorityType::AccountOwner => 2,
+      AuthorityType::CloseAccount => 3,
+    }
+  }
+
+  fn from(index: u8) -> Result<Self, ProgramError> {
+    match index {
+      0 => Ok(AuthorityType::MintTokens),
+      1 => Ok(AuthorityType::FreezeAccount),
+      2 => Ok(AuthorityType::AccountOwner),
+      3 => Ok(AuthorityType::CloseAccount),
+      _ => Err(ProgramError::InvalidInstructionData),
+    }
+  }
+}
+
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenInstruction {
+  InitializeMint {
+    decimals: u8,
+    mint_authority: Pubkey,
+    freeze_authority: COption<Pubkey>,
+  },
+  InitializeAccount,
+  InitializeMultisig {
+    m: u8,
+  },
+  Transfer {
+    amount: u64,
+  },
+  Approve {
+    amount: u64,
+  },
+  Revoke,
+  SetAuthority {
+    authority_type: AuthorityType,
+    new_authority: COption<Pubkey>,
+  },
+  MintTo {
+    amount: u64,
+  },
+  Burn {
+    amount: u64,
+  },
+  CloseAccount,
+  FreezeAccount,
+  ThawAccount,
+  TransferChecked {
+    amount: u64,
+    decimals: u8,
+  },
+  ApproveChecked {
+    amount: u64,
+    decimals: u8,
+  },
+  MintToChecked {
+    amount: u64,
+    decimals: u8,
+  },
+  BurnChecked {
+    amount: u64,
+    decimals: u8,
+  },
+  InitializeAccount2 {
+    owner: Pubkey,
+  },
+  SyncNative,
+  InitializeAccount3 {
+    owner: Pubkey,
+  },
+  InitializeMultisig2 {
+    m: u8,
+  },
+  InitializeMint2 {
+    decimals: u8,
+    mint_authority: Pubkey,
+    freeze_authority: COption<Pubkey>,
+  },
+  GetAccountDataSize,
+  InitializeImmutableOwner,
+  AmountToUiAmount {
+    amount: u64,
+  },
+  UiAmountToAmount {
+    ui_amount: String,
+  },
+}
+
+impl TokenInstruction {
+  pub fn pack(&self) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(size_of::<Self>());
+    match self {
+      &Self::InitializeMint {
+        ref mint_authority,
+        ref freeze_authority,
+        decimals,
+      } => {
+        buf.push(0);
+        buf.push(decimals);
+        buf.extend_from_slice(mint_authority.as_ref());
+        pack_pubkey_option(freeze_authority, &mut buf);
+      }
+      Self::InitializeAccount => buf.push(1),
+      &Self::InitializeMultisig { m } => {
+        buf.push(2);
+        buf.push(m);
+      }
+      &Self::Transfer { amount } => {
+          buf.push(3);
+          buf.extend_from_slice(&amount.to_le_bytes());
+      }
+      &Self::Approve { amount } => {
+          buf.push(4);
+          buf.extend_from_slice(&amount.to_le_bytes());
+      }
+      &Self::MintTo { amount } => {
+          buf.push(7);
+          buf.extend_from_slice(&amount.to_le_bytes());
+      }
+      &Self::Burn { amount } => {
+          buf.push(8);
+          buf.extend_from_slice(&amount.to_le_bytes());
+      }
+      Self::Revoke => buf.push(5),
+      Self::SetAuthority {
+          authority_type,
+          ref new_authority,
+      } => {
+          buf.push(6);
+          buf.push(authority_type.into());
+          pack_pubkey_option(new_authority, &mut buf);
+      }
+      Self::CloseAccount => buf.push(9),
+      Self::FreezeAccount => buf.push(10),
+      Self::ThawAccount => buf.push(11),
+      &Self::TransferChecked { amount, decimals } => {
+        buf.push(12);
+        buf.extend_from_slice(&amount.to_le_bytes());
+        buf.push(decimals);
+      }
+      &Self::ApproveChecked { amount, decimals } => {
+        buf.push(13);
+        buf.extend_from_slice(&amount.to_le_bytes());
+        buf.push(decimals);
+      }
+      &Self::MintToChecked { amount, decimals } => {
+        buf.push(14);
+        buf.extend_from_slice(&amount.to_le_bytes());
+        buf.push(decimals);
+      }
+      &Self::BurnChecked { amount, decimals } => {
+        buf.push(15);
+        buf.extend_from_slice(&amount.to_le_bytes());
+        buf.push(decimals);
+      }
+      &Self::InitializeAccount2 { owner } => {
+        buf.push(16);
+        buf.extend_from_slice(owner.as_ref());
+      }
+      Self::SyncNative => buf.push(17),
+      &Self::InitializeAccount3 { owner } => {
+        buf.push(18);
+        buf.extend_from_slice(owner.as_ref());
+      }
+      &Self::InitializeMultisig2 { m } => {
+        buf.push(19);
+        buf.push(m);
+      }
+      &Self::InitializeMint2 {
+        ref mint_authority,
+        ref freeze_authority,
+        decimals,
+      } => {
+        buf.push(20);
+        buf.push(decimals);
+        buf.extend_from_slice(mint_authority.as_ref());
+        pack_pubkey_option(freeze_authority, &mut buf);
+      }
+      Self::GetAccountDataSize => buf.push(21),
+      Self::InitializeImmutableOwner => buf.push(22),
+      &Self::AmountToUiAmount { amount } => {
+        buf.push(23);
+        buf.extend_from_slice(&amount.to_le_bytes());
+      }
+      Self::UiAmountToAmount { ui_amount } => {
+        buf.push(24);
+        buf.extend_from_slice(ui_amount.as_bytes());
+      }
+    };
+    buf
+  }
+
+  pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+    let (&tag, rest) = input.split_first()
+      .ok_or(ProgramError::InvalidInstructionData)?;
+    Ok(match tag {
+      0 | 20 => {
+        let (&decimals, rest) = rest.split_first()
+          .ok_or(ProgramError::InvalidInstructionData)?;
+        let (mint_authority, rest) = unpack_pubkey(rest)?;
+        let (freeze_authority, _rest) = unpack_pubkey_option(rest)?;
+        match tag {
+          0 => Self::InitializeMint {
+            decimals,
+            mint_authority,
+            freeze_authority,
+          },
+          _ => Self::InitializeMint2 {
+            decimals,
+            mint_authority,
+            freeze_authority,
+          },
+        }
+      }
+      1 => Self::InitializeAccount,
+      2 | 19 => {
+        let &m = rest.first()
+          .ok_or(ProgramError::InvalidInstructionData)?;
+        match tag {
+          2 => Self::InitializeMultisig { m },
+          _ => Self::InitializeMultisig2 { m },
+        }
+      }
+      3 | 4 | 7 | 8 | 23 => {
+        let (amount, _rest) = unpack_u64(rest)?;
+        match tag {
+          3 => Self::Transfer { amount },
+          4 => Self::Approve { amount },
+          7 => Self::MintTo { amount },
+          8 => Self::Burn { amount },
+          _ => Self::AmountToUiAmount { amount },
+        }
+      }
+      5 => Self::Revoke,
+      6 => {
+        let (&authority_type, rest) = rest.split_first()
+          .ok_or(ProgramError::InvalidInstructionData)?;
+        let (new_authority, _rest) = unpack_pubkey_option(rest)?;
+        Self::SetAuthority {
+          authority_type: AuthorityType::from(authority_type)?,
+          new_authority,
+        }
+      }
+      9 => Self::CloseAccount,
+      10 => Self::FreezeAccount,
+      11 => Self::ThawAccount,
+      12..=15 => {
+        let (amount, rest) = unpack_u64(rest)?;
+        let &decimals = rest.first()
+          .ok_or(ProgramError::InvalidInstructionData)?;
+        match tag {
+          12 => Self::TransferChecked { amount, decimals },
+          13 => Self::ApproveChecked { amount, decimals },
+          14 => Self::MintToChecked { amount, decimals },
+          _ => Self::BurnChecked { amount, decimals },
+        }
+      }
+      16 | 18 => {
+        let (owner, _rest) = unpack_pubkey(rest)?;
+        match tag {
+          16 => Self::InitializeAccount2 { owner },
+          _ => Self::InitializeAccount3 { owner },
+        }
+      }
+      17 => Self::SyncNative,
+      21 => Self::GetAccountDataSize,
+      22 => Self::InitializeImmutableOwner,
+      24 => {
+        let ui_amount = std::str::from_utf8(rest)
+          .map_err(|_| ProgramError::InvalidInstructionData)?;
+        Self::UiAmountToAmount {
+          ui_amount: ui_amount.to_string(),
+        }
+      }
+      _ => return Err(ProgramError::InvalidInstructionData),
+    })
+  }
+
+  /// Roles of the fixed accounts expected by the instruction, in order. Accounts
+  /// after these are the signers of a multisig authority.
+  pub fn account_roles(&self) -> &'static [TokenAccountRole] {
+    use TokenAccountRole::*;
+    match self {
+      Self::InitializeMint { .. } => &[Mint, Rent],
+      Self::InitializeAccount => &[Account, Mint, Owner, Rent],
+      Self::InitializeMultisig { .. } => &[Multisig, Rent],
+      Self::Transfer { .. } => &[Source, Destination, Authority],
+      Self::Approve { .. } => &[Source, Delegate, Authority],
+      Self::Revoke => &[Source, Authority],
+      Self::SetAuthority { .. } => &[Account, Authority],
+      Self::MintTo { .. } => &[Mint, Destination, Authority],
+      Self::Burn { .. } => &[Source, Mint, Authority],
+      Self::CloseAccount => &[Account, Destination, Authority],
+      Self::FreezeAccount => &[Account, Mint, Authority],
+      Self::ThawAccount => &[Account, Mint, Authority],
+      Self::TransferChecked { .. } => &[Source, Mint, Destination, Authority],
+      Self::ApproveChecked { .. } => &[Source, Mint, Delegate, Authority],
+      Self::MintToChecked { .. } => &[Mint, Destination, Authority],
+      Self::BurnChecked { .. } => &[Source, Mint, Authority],
+      Self::InitializeAccount2 { .. } => &[Account, Mint, Rent],
+      Self::SyncNative => &[Account],
+      Self::InitializeAccount3 { .. } => &[Account, Mint],
+      Self::InitializeMultisig2 { .. } => &[Multisig],
+      Self::InitializeMint2 { .. } => &[Mint],
+      Self::GetAccountDataSize => &[Mint],
+      Self::InitializeImmutableOwner => &[Account],
+      Self::AmountToUiAmount { .. } => &[Mint],
+      Self::UiAmountToAmount { .. } => &[Mint],
+    }
+  }
+}
+
+/// Role of an account in a token instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TokenAccountRole {
+  /// Token account or mint being initialized or modified
+  Account,
+  Mint,
+  /// Owner of a token account being initialized
+  Owner,
+  Rent,
+  Multisig,
+  /// Signer of a multisig authority, or a signer of a multisig being initialized
+  Signer,
+  Source,
+  Destination,
+  Delegate,
+  /// Owner, delegate or mint/freeze authority signing the instruction
+  Authority,
+}
+
+/// Checks that `program_id` is SPL Token or Token-2022.
+pub fn check_token_program_id(program_id: &Pubkey) -> Result<(), ProgramError> {
+  if *program_id != ID && *program_id != TOKEN_2022_PROGRAM_ID {
+    return Err(ProgramError::IncorrectProgramId);
+  }
+  Ok(())
+}
+
+/// A token instruction with each of its accounts labeled by role.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedTokenInstruction {
+  pub instruction: TokenInstruction,
+  pub accounts: Vec<(TokenAccountRole, AccountMeta)>,
+}
+
+impl DecodedTokenInstruction {
+  /// Returns the first account with the given role
+  pub fn account(&self, role: TokenAccountRole) -> Option<&AccountMeta> {
+    self.accounts.iter()
+      .find(|(account_role, _)| *account_role == role)
+      .map(|(_, account_meta)| account_meta)
+  }
+  /// Returns all accounts with the given role
+  pub fn accounts(&self, role: TokenAccountRole) -> Vec<&AccountMeta> {
+    self.accounts.iter()
+      .filter(|(account_role, _)| *account_role == role)
+      .map(|(_, account_meta)| account_meta)
+      .collect()
+  }
+}
+
+/// Decodes an instruction addressed to the token program.
+pub fn decode_token_instruction(
+  instruction: &Instruction,
+) -> Result<DecodedTokenInstruction, ProgramError> {
+  check_token_program_id(&instruction.program_id)?;
+  let token_instruction = TokenInstruction::unpack(&instruction.data)?;
+  let roles = token_instruction.account_roles();
+  if instruction.accounts.len() < roles.len() {
+    return Err(ProgramError::NotEnoughAccountKeys);
+  }
+  let accounts = instruction.accounts.iter()
+    .enumerate()
+    .map(|(i, account_meta)| {
+      let role = roles.get(i)
+        .copied()
+        .unwrap_or(TokenAccountRole::Signer);
+      (role, account_meta.clone())
+    })
+    .collect();
+  Ok(DecodedTokenInstruction {
+    instruction: token_instruction,
+    accounts,
+  })
+}
+
+/// Turns the single-signer authority of a token instruction into a multisig authority:
+/// the authority account is no longer a signer and `signer_addresses` are appended as signers.
+pub fn multisig_instruction(
+  instruction: Instruction,
+  signer_addresses: &[&Pubkey],
+) -> Result<Instruction, ProgramError> {
+  let mut instruction = instruction;
+  let token_instruction = TokenInstruction::unpack(&instruction.data)?;
+  let authority_index = token_instruction.account_roles()
+    .iter()
+    .position(|role| *role == TokenAccountRole::Authority)
+    .ok_or(ProgramError::InvalidArgument)?;
+  let authority_meta = instruction.accounts.get_mut(authority_index)
+    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+  authority_meta.is_signer = false;
+  for signer_address in signer_addresses {
+    instruction.accounts.push(AccountMeta::new_readonly(**signer_address, true));
+  }
+  Ok(instruction)
+}
+
+pub fn amount_to_ui_amount_instruction(
+  token_program_id: &Pubkey,
+  mint_address: &Pubkey,
+  amount: u64,
+) -> Instruction {
+
+  let data = TokenInstruction::AmountToUiAmount {
+    amount,
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new_readonly(*mint_address, false),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn approve_token_instruction(
+  token_program_id: &Pubkey,
+  owner_address: &Pubkey,
+  source_address: &Pubkey,
+  delegate_address: &Pubkey,
+  amount: u64,
+) -> Instruction {
+
+  let data = TokenInstruction::Approve {
+    amount,
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*source_address, false),
+    AccountMeta::new(*delegate_address, false),
+    AccountMeta::new_readonly(*owner_address, true),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn approve_token_checked_instruction(
+  token_program_id: &Pubkey,
+  owner_address: &Pubkey,
+  source_address: &Pubkey,
+  mint_address: &Pubkey,
+  delegate_address: &Pubkey,
+  amount: u64,
+  decimals: u8,
+) -> Instruction {
+
+  let data = TokenInstruction::ApproveChecked {
+    amount,
+    decimals,
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*source_address, false),
+    AccountMeta::new_readonly(*mint_address, false),
+    AccountMeta::new_readonly(*delegate_address, false),
+    AccountMeta::new_readonly(*owner_address, true),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn burn_token_instruction(
+  token_program_id: &Pubkey,
+  owner_address: &Pubkey,
+  source_address: &Pubkey,
+  mint_address: &Pubkey,
+  amount: u64,
+) -> Instruction {
+
+  let data = TokenInstruction::Burn {
+    amount,
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*source_address, false),
+    AccountMeta::new(*mint_address, false),
+    AccountMeta::new_readonly(*owner_address, true),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn burn_token_checked_instruction(
+  token_program_id: &Pubkey,
+  owner_address: &Pubkey,
+  source_address: &Pubkey,
+  mint_address: &Pubkey,
+  amount: u64,
+  decimals: u8,
+) -> Instruction {
+
+  let data = TokenInstruction::BurnChecked {
+    amount,
+    decimals,
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*source_address, false),
+    AccountMeta::new(*mint_address, false),
+    AccountMeta::new_readonly(*owner_address, true),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn close_token_account_instruction(
+  token_program_id: &Pubkey,
+  owner_address: &Pubkey,
+  source_address: &Pubkey,
+) -> Instruction {
+  close_token_account_with_destination_instruction(
+    token_program_id,
+    owner_address,
+    source_address,
+    owner_address,
+  )
+}
+
+/// Closes `source_address` sending its lamports to `destination_address`, signed by the
+/// owner or the close authority of the account.
+pub fn close_token_account_with_destination_instruction(
+  token_program_id: &Pubkey,
+  authority_address: &Pubkey,
+  source_address: &Pubkey,
+  destination_address: &Pubkey,
+) -> Instruction {
+
+  let data = TokenInstruction::CloseAccount.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*source_address, false),
+    AccountMeta::new(*destination_address, false),
+    AccountMeta::new_readonly(*authority_address, true),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn change_mint_authority_instruction(
+  token_program_id: &Pubkey,
+  authority_address: &Pubkey,
+  mint_address: &Pubkey,
+  authority_type: AuthorityType,
+  new_authority_address: Option<&Pubkey>,
+) -> Instruction {
+  set_authority_instruction(
+    token_program_id,
+    authority_address,
+    mint_address,
+    authority_type,
+    new_authority_address,
+  )
+}
+
+pub fn freeze_token_account_instruction(
+  token_program_id: &Pubkey,
+  authority_address: &Pubkey,
+  mint_address: &Pubkey,
+  token_account_address: &Pubkey,
+) -> Instruction {
+
+  let data = TokenInstruction::FreezeAccount.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*token_account_address, false),
+    AccountMeta::new_readonly(*mint_address, false),
+    AccountMeta::new_readonly(*authority_address, true),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn get_account_data_size_instruction(
+  token_program_id: &Pubkey,
+  mint_address: &Pubkey,
+) -> Instruction {
+
+  let data = TokenInstruction::GetAccountDataSize.pack();
+
+  let accounts = vec![
+    AccountMeta::new_readonly(*mint_address, false),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn initialize_immutable_owner_instruction(
+  token_program_id: &Pubkey,
+  token_account_address: &Pubkey,
+) -> Instruction {
+
+  let data = TokenInstruction::InitializeImmutableOwner.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*token_account_address, false),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn initialize_token_account_instruction(
+  token_program_id: &Pubkey,
+  owner_address: &Pubkey,
+  token_account_address: &Pubkey,
+  mint_address: &Pubkey,
+) -> Instruction {
+
+  let data = TokenInstruction::InitializeAccount3 {
+    owner: *owner_address,
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*token_account_address, false),
+    AccountMeta::new_readonly(*mint_address, false),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+/// Initializes a token account with the original `InitializeAccount`, which requires
+/// the owner and the rent sysvar as accounts.
+pub fn initialize_token_account_v1_instruction(
+  token_program_id: &Pubkey,
+  owner_address: &Pubkey,
+  token_account_address: &Pubkey,
+  mint_address: &Pubkey,
+) -> Instruction {
+
+  let data = TokenInstruction::InitializeAccount.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*token_account_address, false),
+    AccountMeta::new_readonly(*mint_address, false),
+    AccountMeta::new_readonly(*owner_address, false),
+    AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+/// Initializes a token account with `InitializeAccount2`, which requires the rent sysvar.
+pub fn initialize_token_account_v2_instruction(
+  token_program_id: &Pubkey,
+  owner_address: &Pubkey,
+  token_account_address: &Pubkey,
+  mint_address: &Pubkey,
+) -> Instruction {
+
+  let data = TokenInstruction::InitializeAccount2 {
+    owner: *owner_address,
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*token_account_address, false),
+    AccountMeta::new_readonly(*mint_address, false),
+    AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn initialize_token_mint_instruction(
+  token_program_id: &Pubkey,
+  mint_address: &Pubkey,
+  decimals: u8,
+  mint_authority_address: &Pubkey,
+  freeze_authority_address: Option<&Pubkey>,
+) -> Instruction {
+
+  let data = TokenInstruction::InitializeMint {
+    decimals,
+    mint_authority: *mint_authority_address,
+    freeze_authority: match freeze_authority_address {
+      Some(ref pubkey) => COption::Some(**pubkey),
+      None => COption::None,
+    },
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*mint_address, false),
+    AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+/// Initializes a mint with `InitializeMint2`, which does not require the rent sysvar.
+pub fn initialize_token_mint_v2_instruction(
+  token_program_id: &Pubkey,
+  mint_address: &Pubkey,
+  decimals: u8,
+  mint_authority_address: &Pubkey,
+  freeze_authority_address: Option<&Pubkey>,
+) -> Instruction {
+
+  let data = TokenInstruction::InitializeMint2 {
+    decimals,
+    mint_authority: *mint_authority_address,
+    freeze_authority: match freeze_authority_address {
+      Some(ref pubkey) => COption::Some(**pubkey),
+      None => COption::None,
+    },
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*mint_address, false),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn initialize_token_multisig_instruction(
+  token_program_id: &Pubkey,
+  multisig_address: &Pubkey,
+  signer_addresses: &[&Pubkey],
+  m: u8,
+) -> Instruction {
+
+  let data = TokenInstruction::InitializeMultisig {
+    m,
+  }.pack();
+
+  let mut accounts = vec![
+    AccountMeta::new(*multisig_address, false),
+    AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+  ];
+  for signer_address in signer_addresses {
+    accounts.push(AccountMeta::new_readonly(**signer_address, false));
+  }
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+/// Initializes a multisig with `InitializeMultisig2`, which does not require the rent sysvar.
+pub fn initialize_token_multisig_v2_instruction(
+  token_program_id: &Pubkey,
+  multisig_address: &Pubkey,
+  signer_addresses: &[&Pubkey],
+  m: u8,
+) -> Instruction {
+
+  let data = TokenInstruction::InitializeMultisig2 {
+    m,
+  }.pack();
+
+  let mut accounts = vec![
+    AccountMeta::new(*multisig_address, false),
+  ];
+  for signer_address in signer_addresses {
+    accounts.push(AccountMeta::new_readonly(**signer_address, false));
+  }
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn mint_token_instruction(
+  token_program_id: &Pubkey,
+  authority_address: &Pubkey,
+  mint_address: &Pubkey,
+  destination_address: &Pubkey,
+  amount: u64,
+) -> Instruction {
+
+  let data = TokenInstruction::MintTo {
+    amount,
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*mint_address, false),
+    AccountMeta::new(*destination_address, false),
+    AccountMeta::new_readonly(*authority_address, true),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn mint_token_checked_instruction(
+  token_program_id: &Pubkey,
+  authority_address: &Pubkey,
+  mint_address: &Pubkey,
+  destination_address: &Pubkey,
+  amount: u64,
+  decimals: u8,
+) -> Instruction {
+
+  let data = TokenInstruction::MintToChecked {
+    amount,
+    decimals,
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*mint_address, false),
+    AccountMeta::new(*destination_address, false),
+    AccountMeta::new_readonly(*authority_address, true),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn revoke_token_instruction(
+  token_program_id: &Pubkey,
+  owner_address: &Pubkey,
+  source_address: &Pubkey,
+) -> Instruction {
+
+  let data = TokenInstruction::Revoke.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*source_address, false),
+    AccountMeta::new_readonly(*owner_address, true),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+/// Sets an authority of a mint (`MintTokens`, `FreezeAccount`) or of a token account
+/// (`AccountOwner`, `CloseAccount`).
+pub fn set_authority_instruction(
+  token_program_id: &Pubkey,
+  authority_address: &Pubkey,
+  account_address: &Pubkey,
+  authority_type: AuthorityType,
+  new_authority_address: Option<&Pubkey>,
+) -> Instruction {
+
+  let data = TokenInstruction::SetAuthority {
+    authority_type,
+    new_authority: match new_authority_address {
+      Some(ref pubkey) => COption::Some(**pubkey),
+      None => COption::None,
+    },
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*account_address, false),
+    AccountMeta::new_readonly(*authority_address, true),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn sync_native_instruction(
+  token_program_id: &Pubkey,
+  token_account_address: &Pubkey,
+) -> Instruction {
+
+  let data = TokenInstruction::SyncNative.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*token_account_address, false),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn thaw_token_account_instruction(
+  token_program_id: &Pubkey,
+  authority_address: &Pubkey,
+  mint_address: &Pubkey,
+  token_account_address: &Pubkey,
+) -> Instruction {
+
+  let data = TokenInstruction::ThawAccount.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*token_account_address, false),
+    AccountMeta::new_readonly(*mint_address, false),
+    AccountMeta::new_readonly(*authority_address, true),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn transfer_token_instruction(
+  token_program_id: &Pubkey,
+  owner_address: &Pubkey,
+  source_address: &Pubkey,
+  destination_address: &Pubkey,
+  amount: u64,
+) -> Instruction {
+
+  let data = TokenInstruction::Transfer {
+    amount,
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*source_address, false),
+    AccountMeta::new(*destination_address, false),
+    AccountMeta::new_readonly(*owner_address, true),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn transfer_token_checked_instruction(
+  token_program_id: &Pubkey,
+  owner_address: &Pubkey,
+  source_address: &Pubkey,
+  mint_address: &Pubkey,
+  destination_address: &Pubkey,
+  amount: u64,
+  decimals: u8,
+) -> Instruction {
+
+  let data = TokenInstruction::TransferChecked {
+    amount,
+    decimals,
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new(*source_address, false),
+    AccountMeta::new_readonly(*mint_address, false),
+    AccountMeta::new(*destination_address, false),
+    AccountMeta::new_readonly(*owner_address, true),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+pub fn ui_amount_to_amount_instruction(
+  token_program_id: &Pubkey,
+  mint_address: &Pubkey,
+  ui_amount: &str,
+) -> Instruction {
+
+  let data = TokenInstruction::UiAmountToAmount {
+    ui_amount: ui_amount.to_string(),
+  }.pack();
+
+  let accounts = vec![
+    AccountMeta::new_readonly(*mint_address, false),
+  ];
+
+  Instruction {
+    accounts,
+    data,
+    program_id: *token_program_id,
+  }
+}
+
+/// Formats base units with all `decimals` digits, i.e. `1.500000` for `1_500_000` and 6 decimals.
+pub fn amount_to_ui_amount_string(amount: u64, decimals: u8) -> String {
+  let decimals = decimals as usize;
+  if decimals == 0 {
+    return amount.to_string();
+  }
+  let mut ui_amount = format!("{:01$}", amount, decimals + 1);
+  ui_amount.insert(ui_amount.len() - decimals, '.');
+  ui_amount
+}
+
+/// Formats base units without trailing zeros, i.e. `1.5` for `1_500_000` and 6 decimals,
+/// like the token program and the `uiAmountString` of RPC responses.
+pub fn amount_to_ui_amount_string_trimmed(amount: u64, decimals: u8) -> String {
+  let ui_amount = amount_to_ui_amount_string(amount, decimals);
+  if decimals == 0 {
+    return ui_amount;
+  }
+  ui_amount.trim_end_matches('0')
+    .trim_end_matches('.')
+    .to_string()
+}
+
+/// Parses a decimal UI amount into base units without going through `f64`.
+pub fn try_ui_amount_into_amount(ui_amount: &str, decimals: u8) -> Result<u64, ProgramError> {
+  let decimals = decimals as usize;
+  let mut parts = ui_amount.split('.');
+  let mut amount = parts.next().unwrap_or("").to_string();
+  let after_decimal = parts.next()
+    .unwrap_or("")
+    .trim_end_matches('0');
+  // `u64::from_str` accepts a leading `+`, signs are rejected here
+  if ui_amount.contains(['+', '-'])
+    || (amount.is_empty() && after_decimal.is_empty())
+    || parts.next().is_some()
+    || after_decimal.len() > decimals
+  {
+    return Err(ProgramError::InvalidArgument);
+  }
+  amount.push_str(after_decimal);
+  for _ in after_decimal.len()..decimals {
+    amount.push('0');
+  }
+  amount.parse::<u64>()
+    .map_err(|_| ProgramError::InvalidArgument)
+}
+
+// HELPERS
+fn pack_pubkey_option(value: &COption<Pubkey>, buf: &mut Vec<u8>) {
+  match *value {
+    COption::Some(ref key) => {
+      buf.push(1);
+      buf.extend_from_slice(&key.to_bytes());
+    }
+    COption::None => buf.push(0),
+  }
+}
+
+fn unpack_pubkey(input: &[u8]) -> Result<(Pubkey, &[u8]), ProgramError> {
+  if input.len() < 32 {
+    return Err(ProgramError::InvalidInstructionData);
+  }
+  let (key, rest) = input.split_at(32);
+  let key = Pubkey::try_from(key)
+    .map_err(|_| ProgramError::InvalidInstructionData)?;
+  Ok((key, rest))
+}
+
+fn unpack_pubkey_option(input: &[u8]) -> Result<(COption<Pubkey>, &[u8]), ProgramError> {
+  match input.split_first() {
+    Some((&0, rest)) => Ok((COption::None, rest)),
+    Some((&1, rest)) => {
+      let (key, rest) = unpack_pubkey(rest)?;
+      Ok((COption::Some(key), rest))
+    }
+    _ => Err(ProgramError::InvalidInstructionData),
+  }
+}
+
+fn unpack_u64(input: &[u8]) -> Result<(u64, &[u8]), ProgramError> {
+  if input.len() < 8 {
+    return Err(ProgramError::InvalidInstructionData);
+  }
+  let (amount, rest) = input.split_at(8);
+  Ok((u64::from_le_bytes(amount.try_into().unwrap()), rest))
+}
+
+fn pack_coption_key(src: &COption<Pubkey>, dst: &mut [u8; 36]) {
+  let (tag, body) = mut_array_refs![dst, 4, 32];
+  match src {
+    COption::Some(key) => {
+      *tag = [1, 0, 0, 0];
+      body.copy_from_slice(key.as_ref());
+    }
+    COption::None => {
+      *tag = [0; 4];
+    }
+  }
+}
+
+fn unpack_coption_key(src: &[u8; 36]) -> Result<COption<Pubkey>, ProgramError> {
+  let (tag, body) = array_refs![src, 4, 32];
+  match *tag {
+    [0, 0, 0, 0] => Ok(COption::None),
+    [1, 0, 0, 0] => Ok(COption::Some(Pubkey::new_from_array(*body))),
+    _ => Err(ProgramError::InvalidAccountData),
+  }
+}
+
+fn pack_coption_u64(src: &COption<u64>, dst: &mut [u8; 12]) {
+  let (tag, body) = mut_array_refs![dst, 4, 8];
+  match src {
+    COption::Some(amount) => {
+      *tag = [1, 0, 0, 0];
+      *body = amount.to_le_bytes();
+    }
+    COption::None => {
+      *tag = [0; 4];
+    }
+  }
+}
+fn unpack_coption_u64(src: &[u8; 12]) -> Result<COption<u64>, ProgramError> {
+  let (tag, body) = array_refs![src, 4, 8];
+  match *tag {
+    [0, 0, 0, 0] => Ok(COption::None),
+    [1, 0, 0, 0] => Ok(COption::Some(u64::from_le_bytes(*body))),
+    _ => Err(ProgramError::InvalidAccountData),
+  }
+}
@@ -0,0 +1,298 @@
+use arrayref::{
+  array_ref,
+  array_refs,
+};
+use num_enum::{
+  TryFromPrimitive,
+};
+use solana_sdk::{
+  program_error::{
+    ProgramError,
+  },
+  program_pack::{
+    IsInitialized,
+    Pack,
+  },
+  pubkey::{
+    Pubkey,
+  },
+};
+use super::{
+  spl_token::{
+    AccountState,
+    TokenAccount,
+    TokenMint,
+    TOKEN_ACCOUNT_LENGTH,
+    TOKEN_MINT_LENGTH,
+    TOKEN_MULTISIG_LENGTH,
+  },
+};
+
+// TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb
+pub const ID: Pubkey = Pubkey::new_from_array([6,221,246,225,238,117,143,222,24,66,93,188,228,108,205,218,182,26,252,77,131,185,13,39,254,189,249,40,216,161,139,252]);
+
+/// Offset of the account type byte, right after the base token account layout.
+pub const ACCOUNT_TYPE_OFFSET: usize = TOKEN_ACCOUNT_LENGTH;
+/// Length of the type and length fields preceding each extension.
+pub const TLV_HEADER_LENGTH: usize = 4;
+
+/// Type of a Token-2022 account, stored after the base layout when extensions are present.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, TryFromPrimitive)]
+pub enum TokenAccountType {
+  Uninitialized,
+  Mint,
+  Account,
+}
+
+/// Extension types as stored in the TLV area.
+#[repr(u16)]
+#[derive(Clone, Copy, Debug, PartialEq, TryFromPrimitive)]
+pub enum ExtensionType {
+  Uninitialized,
+  TransferFeeConfig,
+  TransferFeeAmount,
+  MintCloseAuthority,
+  ConfidentialTransferMint,
+  ConfidentialTransferAccount,
+  DefaultAccountState,
+  ImmutableOwner,
+  MemoTransfer,
+  NonTransferable,
+  InterestBearingConfig,
+}
+
+/// Transfer fee applying from `epoch` onwards.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TransferFee {
+  pub epoch: u64,
+  pub maximum_fee: u64,
+  pub transfer_fee_basis_points: u16,
+}
+
+impl TransferFee {
+  /// Computes the fee withheld on a transfer of `amount`
+  pub fn calculate_fee(&self, amount: u64) -> u64 {
+    if self.transfer_fee_basis_points == 0 || amount == 0 {
+      return 0;
+    }
+    let fee = (amount as u128 * self.transfer_fee_basis_points as u128 + 9_999) / 10_000;
+    (fee as u64).min(self.maximum_fee)
+  }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TransferFeeConfig {
+  pub transfer_fee_config_authority: Option<Pubkey>,
+  pub withdraw_withheld_authority: Option<Pubkey>,
+  /// Fees withheld on the mint by harvesting token accounts
+  pub withheld_amount: u64,
+  pub older_transfer_fee: TransferFee,
+  pub newer_transfer_fee: TransferFee,
+}
+
+impl TransferFeeConfig {
+  /// Returns the fee in effect at `epoch`
+  pub fn get_epoch_fee(&self, epoch: u64) -> &TransferFee {
+    if epoch >= self.newer_transfer_fee.epoch {
+      &self.newer_transfer_fee
+    } else {
+      &self.older_transfer_fee
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct InterestBearingConfig {
+  pub rate_authority: Option<Pubkey>,
+  pub initialization_timestamp: i64,
+  pub pre_update_average_rate: i16,
+  pub last_update_timestamp: i64,
+  /// Current rate in basis points
+  pub current_rate: i16,
+}
+
+/// Decoded entry of the Token-2022 extension area.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenExtension {
+  TransferFeeConfig(TransferFeeConfig),
+  TransferFeeAmount {
+    withheld_amount: u64,
+  },
+  MintCloseAuthority {
+    close_authority: Option<Pubkey>,
+  },
+  DefaultAccountState {
+    state: AccountState,
+  },
+  ImmutableOwner,
+  MemoTransfer {
+    require_incoming_transfer_memos: bool,
+  },
+  NonTransferable,
+  InterestBearingConfig(InterestBearingConfig),
+  /// Extension this library does not decode, with its raw value
+  Unknown {
+    extension_type: u16,
+    data: Vec<u8>,
+  },
+}
+
+impl TokenExtension {
+  pub fn unpack(extension_type: u16, data: &[u8]) -> Result<Self, ProgramError> {
+    let extension_type_option = ExtensionType::try_from_primitive(extension_type).ok();
+    Ok(match extension_type_option {
+      Some(ExtensionType::TransferFeeConfig) => {
+        let data = array_ref![check_length(data, 108)?, 0, 108];
+        let (config_authority, withdraw_authority, withheld_amount, older_transfer_fee, newer_transfer_fee) =
+          array_refs![data, 32, 32, 8, 18, 18];
+        Self::TransferFeeConfig(TransferFeeConfig {
+          transfer_fee_config_authority: unpack_optional_pubkey(config_authority),
+          withdraw_withheld_authority: unpack_optional_pubkey(withdraw_authority),
+          withheld_amount: u64::from_le_bytes(*withheld_amount),
+          older_transfer_fee: unpack_transfer_fee(older_transfer_fee),
+          newer_transfer_fee: unpack_transfer_fee(newer_transfer_fee),
+        })
+      },
+      Some(ExtensionType::TransferFeeAmount) => {
+        let data = array_ref![check_length(data, 8)?, 0, 8];
+        Self::TransferFeeAmount {
+          withheld_amount: u64::from_le_bytes(*data),
+        }
+      },
+      Some(ExtensionType::MintCloseAuthority) => {
+        let data = array_ref![check_length(data, 32)?, 0, 32];
+        Self::MintCloseAuthority {
+          close_authority: unpack_optional_pubkey(data),
+        }
+      },
+      Some(ExtensionType::DefaultAccountState) => {
+        let data = check_length(data, 1)?;
+        Self::DefaultAccountState {
+          state: AccountState::try_from_primitive(data[0])
+            .or(Err(ProgramError::InvalidAccountData))?,
+        }
+      },
+      Some(ExtensionType::ImmutableOwner) => {
+        check_length(data, 0)?;
+        Self::ImmutableOwner
+      },
+      Some(ExtensionType::MemoTransfer) => {
+        let data = check_length(data, 1)?;
+        Self::MemoTransfer {
+          require_incoming_transfer_memos: data[0] != 0,
+        }
+      },
+      Some(ExtensionType::NonTransferable) => {
+        check_length(data, 0)?;
+        Self::NonTransferable
+      },
+      Some(ExtensionType::InterestBearingConfig) => {
+        let data = array_ref![check_length(data, 52)?, 0, 52];
+        let (rate_authority, initialization_timestamp, pre_update_average_rate, last_update_timestamp, current_rate) =
+          array_refs![data, 32, 8, 2, 8, 2];
+        Self::InterestBearingConfig(InterestBearingConfig {
+          rate_authority: unpack_optional_pubkey(rate_authority),
+          initialization_timestamp: i64::from_le_bytes(*initialization_timestamp),
+          pre_update_average_rate: i16::from_le_bytes(*pre_update_average_rate),
+          last_update_timestamp: i64::from_le_bytes(*last_update_timestamp),
+          current_rate: i16::from_le_bytes(*current_rate),
+        })
+      },
+      _ => Self::Unknown {
+        extension_type,
+        data: data.to_vec(),
+      },
+    })
+  }
+}
+
+/// Unpacks a token account of either token program, ignoring any extension data.
+pub fn unpack_token_account(data: &[u8]) -> Result<TokenAccount, ProgramError> {
+  check_account_type(data, TOKEN_ACCOUNT_LENGTH, TokenAccountType::Account)?;
+  let token_account = TokenAccount::unpack_from_slice(&data[..TOKEN_ACCOUNT_LENGTH])?;
+  if !token_account.is_initialized() {
+    return Err(ProgramError::UninitializedAccount);
+  }
+  Ok(token_account)
+}
+
+/// Unpacks a mint of either token program, ignoring any extension data.
+pub fn unpack_token_mint(data: &[u8]) -> Result<TokenMint, ProgramError> {
+  check_account_type(data, TOKEN_MINT_LENGTH, TokenAccountType::Mint)?;
+  let token_mint = TokenMint::unpack_from_slice(&data[..TOKEN_MINT_LENGTH])?;
+  if !token_mint.is_initialized() {
+    return Err(ProgramError::UninitializedAccount);
+  }
+  Ok(token_mint)
+}
+
+/// Parses the TLV extension area of a mint or token account. Accounts without extensions,
+/// including every classic SPL Token account, yield an empty list.
+pub fn unpack_token_extensions(data: &[u8]) -> Result<Vec<TokenExtension>, ProgramError> {
+  let mut extensions = Vec::new();
+  if data.len() <= ACCOUNT_TYPE_OFFSET {
+    return Ok(extensions);
+  }
+  if data.len() == TOKEN_MULTISIG_LENGTH {
+    return Err(ProgramError::InvalidAccountData);
+  }
+  let mut offset = ACCOUNT_TYPE_OFFSET + 1;
+  while offset + TLV_HEADER_LENGTH <= data.len() {
+    let header = array_ref![data, offset, TLV_HEADER_LENGTH];
+    let (extension_type, length) = array_refs![header, 2, 2];
+    let extension_type = u16::from_le_bytes(*extension_type);
+    let length = u16::from_le_bytes(*length) as usize;
+    if extension_type == ExtensionType::Uninitialized as u16 {
+      break;
+    }
+    let value_start = offset + TLV_HEADER_LENGTH;
+    let value = data.get(value_start..value_start + length)
+      .ok_or(ProgramError::InvalidAccountData)?;
+    extensions.push(TokenExtension::unpack(extension_type, value)?);
+    offset = value_start + length;
+  }
+  Ok(extensions)
+}
+
+// HELPERS
+fn check_account_type(data: &[u8], base_length: usize, account_type: TokenAccountType) -> Result<(), ProgramError> {
+  if data.len() == base_length {
+    return Ok(());
+  }
+  if data.len() <= ACCOUNT_TYPE_OFFSET || data.len() == TOKEN_MULTISIG_LENGTH {
+    return Err(ProgramError::InvalidAccountData);
+  }
+  if data[base_length..ACCOUNT_TYPE_OFFSET].iter().any(|byte| *byte != 0) {
+    return Err(ProgramError::InvalidAccountData);
+  }
+  if data[ACCOUNT_TYPE_OFFSET] != account_type as u8 {
+    return Err(ProgramError::InvalidAccountData);
+  }
+  Ok(())
+}
+
+fn check_length(data: &[u8], length: usize) -> Result<&[u8], ProgramError> {
+  if data.len() != length {
+    return Err(ProgramError::InvalidAccountData);
+  }
+  Ok(data)
+}
+
+fn unpack_optional_pubkey(src: &[u8; 32]) -> Option<Pubkey> {
+  let pubkey = Pubkey::new_from_array(*src);
+  if pubkey == Pubkey::default() {
+    None
+  } else {
+    Some(pubkey)
+  }
+}
+
+fn unpack_transfer_fee(src: &[u8; 18]) -> TransferFee {
+  let (epoch, maximum_fee, transfer_fee_basis_points) = array_refs![src, 8, 8, 2];
+  TransferFee {
+    epoch: u64::from_le_bytes(*epoch),
+    maximum_fee: u64::from_le_bytes(*maximum_fee),
+    transfer_fee_basis_points: u16::from_le_bytes(*transfer_fee_basis_points),
+  }
+}
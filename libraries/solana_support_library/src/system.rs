@@ -0,0 +1,365 @@
+use solana_program_test::{
+  ProgramTestContext,
+};
+use solana_sdk::{
+  account_utils::{
+    StateMut,
+  },
+  hash::{
+    Hash,
+  },
+  instruction::{
+    Instruction,
+  },
+  nonce::{
+    state::{
+      Data as NonceData,
+      State as NonceState,
+      Versions as NonceVersions,
+    },
+  },
+  pubkey::{
+    Pubkey,
+  },
+  signature::{
+    Keypair,
+    Signer,
+  },
+  system_instruction,
+  system_program::{
+    ID as SYSTEM_PROGRAM_ID,
+  },
+  transaction::{
+    Transaction,
+  },
+};
+use super::{
+  context::{
+    get_payer,
+    try_get_rent,
+    try_process_transaction,
+  },
+  error::{
+    FrameworkError,
+    FrameworkResult,
+  },
+  spl_token::{
+    ID as TOKEN_PROGRAM_ID,
+  },
+  spl_token_2022::{
+    ID as TOKEN_2022_PROGRAM_ID,
+  },
+};
+
+pub async fn airdrop_lamport(
+  context: &mut ProgramTestContext,
+  recipient: &Pubkey,
+  amount: u64,
+) {
+  try_airdrop_lamport(context, recipient, amount)
+    .await.unwrap();
+}
+
+pub async fn try_airdrop_lamport(
+  context: &mut ProgramTestContext,
+  recipient: &Pubkey,
+  amount: u64,
+) -> FrameworkResult<()> {
+  let instruction = system_instruction::transfer(
+    &context.payer.pubkey(),
+    recipient,
+    amount,
+  );
+  let payer = get_payer(&context);
+  try_process_transaction(
+    context,
+    &payer,
+    &[instruction],
+    &[&payer],
+  ).await
+}
+
+pub async fn get_account_type(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> u8 {
+  try_get_account_type(context, address)
+    .await.unwrap()
+}
+
+pub async fn try_get_account_type(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> FrameworkResult<u8> {
+  let account_option = context.banks_client
+    .get_account(*address)
+    .await?;
+  if account_option.is_none() {
+    return Ok(0u8);
+  }
+  let account_info = account_option.unwrap();
+  if account_info.owner == SYSTEM_PROGRAM_ID {
+    return Ok(1u8);
+  }
+  if account_info.owner == TOKEN_PROGRAM_ID || account_info.owner == TOKEN_2022_PROGRAM_ID {
+    return Ok(2u8);
+  }
+
+  Ok(255u8)
+}
+
+pub async fn get_account_balance(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> u64 {
+  try_get_account_balance(context, address)
+    .await.unwrap()
+}
+
+pub async fn try_get_account_balance(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> FrameworkResult<u64> {
+  let balance = context.banks_client
+    .get_balance(*address)
+    .await?;
+  Ok(balance)
+}
+
+pub async fn advance_nonce_account(
+  context: &mut ProgramTestContext,
+  nonce_account: &Pubkey,
+  authority: &Keypair,
+) {
+  try_advance_nonce_account(context, nonce_account, authority)
+    .await.unwrap();
+}
+
+/// Replaces the stored nonce, the bank must have moved to a new blockhash since the last advance.
+pub async fn try_advance_nonce_account(
+  context: &mut ProgramTestContext,
+  nonce_account: &Pubkey,
+  authority: &Keypair,
+) -> FrameworkResult<()> {
+  let instruction = system_instruction::advance_nonce_account(
+    nonce_account,
+    &authority.pubkey(),
+  );
+  let payer = get_payer(&context);
+  try_process_transaction(
+    context,
+    &payer,
+    &[instruction],
+    &[&payer, authority],
+  ).await
+}
+
+pub async fn authorize_nonce_account(
+  context: &mut ProgramTestContext,
+  nonce_account: &Pubkey,
+  authority: &Keypair,
+  new_authority: &Pubkey,
+) {
+  try_authorize_nonce_account(context, nonce_account, authority, new_authority)
+    .await.unwrap();
+}
+
+pub async fn try_authorize_nonce_account(
+  context: &mut ProgramTestContext,
+  nonce_account: &Pubkey,
+  authority: &Keypair,
+  new_authority: &Pubkey,
+) -> FrameworkResult<()> {
+  let instruction = system_instruction::authorize_nonce_account(
+    nonce_account,
+    &authority.pubkey(),
+    new_authority,
+  );
+  let payer = get_payer(&context);
+  try_process_transaction(
+    context,
+    &payer,
+    &[instruction],
+    &[&payer, authority],
+  ).await
+}
+
+pub async fn create_nonce_account(
+  context: &mut ProgramTestContext,
+  nonce_account: &Keypair,
+  authority: &Pubkey,
+) {
+  try_create_nonce_account(context, nonce_account, authority)
+    .await.unwrap();
+}
+
+/// Creates a rent-exempt nonce account funded by the context payer.
+pub async fn try_create_nonce_account(
+  context: &mut ProgramTestContext,
+  nonce_account: &Keypair,
+  authority: &Pubkey,
+) -> FrameworkResult<()> {
+  let rent = try_get_rent(context)
+    .await?;
+  let payer = get_payer(&context);
+  let instructions = system_instruction::create_nonce_account(
+    &payer.pubkey(),
+    &nonce_account.pubkey(),
+    authority,
+    rent.minimum_balance(NonceState::size()),
+  );
+  try_process_transaction(
+    context,
+    &payer,
+    &instructions,
+    &[&payer, nonce_account],
+  ).await
+}
+
+/// Builds a transaction that uses the durable `nonce` in place of a recent blockhash, with
+/// the required advance instruction prepended. It can be signed offline and sent at any time
+/// until the nonce is advanced.
+pub fn create_nonce_transaction(
+  payer: &Keypair,
+  nonce_account: &Pubkey,
+  nonce_authority: &Keypair,
+  nonce: &Hash,
+  instructions: &[Instruction],
+  signers: &[&Keypair],
+) -> Transaction {
+  let mut nonce_instructions = vec![
+    system_instruction::advance_nonce_account(
+      nonce_account,
+      &nonce_authority.pubkey(),
+    ),
+  ];
+  nonce_instructions.extend_from_slice(instructions);
+  let mut nonce_signers = vec![payer, nonce_authority];
+  nonce_signers.extend_from_slice(signers);
+  Transaction::new_signed_with_payer(
+    &nonce_instructions,
+    Some(&payer.pubkey()),
+    &nonce_signers,
+    *nonce,
+  )
+}
+
+pub async fn get_nonce(
+  context: &mut ProgramTestContext,
+  nonce_account: &Pubkey,
+) -> Hash {
+  try_get_nonce(context, nonce_account)
+    .await.unwrap()
+}
+
+/// Returns the durable nonce to sign with in place of a recent blockhash.
+pub async fn try_get_nonce(
+  context: &mut ProgramTestContext,
+  nonce_account: &Pubkey,
+) -> FrameworkResult<Hash> {
+  let nonce_data = try_get_nonce_data(context, nonce_account)
+    .await?;
+  Ok(nonce_data.blockhash())
+}
+
+pub async fn get_nonce_data(
+  context: &mut ProgramTestContext,
+  nonce_account: &Pubkey,
+) -> NonceData {
+  try_get_nonce_data(context, nonce_account)
+    .await.unwrap()
+}
+
+/// Reads the authority, stored nonce and fee calculator of an initialized nonce account.
+pub async fn try_get_nonce_data(
+  context: &mut ProgramTestContext,
+  nonce_account: &Pubkey,
+) -> FrameworkResult<NonceData> {
+  let account = context.banks_client
+    .get_account(*nonce_account)
+    .await?
+    .ok_or(FrameworkError::AccountNotFound(*nonce_account))?;
+  if account.owner != SYSTEM_PROGRAM_ID {
+    return Err(FrameworkError::InvalidAccountOwner {
+      address: *nonce_account,
+      expected: SYSTEM_PROGRAM_ID,
+      actual: account.owner,
+    });
+  }
+  let versions: NonceVersions = account.state()
+    .map_err(|err| FrameworkError::InvalidAccountData {
+      address: *nonce_account,
+      reason: err.to_string(),
+    })?;
+  match versions.state() {
+    NonceState::Initialized(nonce_data) => Ok(nonce_data.clone()),
+    NonceState::Uninitialized => Err(FrameworkError::InvalidAccountData {
+      address: *nonce_account,
+      reason: "nonce account is not initialized".to_string(),
+    }),
+  }
+}
+
+pub async fn transfer_lamport(
+  context: &mut ProgramTestContext,
+  sender: &Keypair,
+  recipient: &Pubkey,
+  amount: u64,
+) {
+  try_transfer_lamport(context, sender, recipient, amount)
+    .await.unwrap();
+}
+
+pub async fn try_transfer_lamport(
+  context: &mut ProgramTestContext,
+  sender: &Keypair,
+  recipient: &Pubkey,
+  amount: u64,
+) -> FrameworkResult<()> {
+  let instruction = system_instruction::transfer(
+    &sender.pubkey(),
+    recipient,
+    amount,
+  );
+  let payer = get_payer(&context);
+  try_process_transaction(
+    context,
+    &payer,
+    &[instruction],
+    &[&payer, &sender],
+  ).await
+}
+
+pub async fn withdraw_nonce_account(
+  context: &mut ProgramTestContext,
+  nonce_account: &Pubkey,
+  authority: &Keypair,
+  recipient: &Pubkey,
+  amount: u64,
+) {
+  try_withdraw_nonce_account(context, nonce_account, authority, recipient, amount)
+    .await.unwrap();
+}
+
+/// Withdraws lamports from the nonce account, withdrawing the whole balance closes it.
+pub async fn try_withdraw_nonce_account(
+  context: &mut ProgramTestContext,
+  nonce_account: &Pubkey,
+  authority: &Keypair,
+  recipient: &Pubkey,
+  amount: u64,
+) -> FrameworkResult<()> {
+  let instruction = system_instruction::withdraw_nonce_account(
+    nonce_account,
+    &authority.pubkey(),
+    recipient,
+    amount,
+  );
+  let payer = get_payer(&context);
+  try_process_transaction(
+    context,
+    &payer,
+    &[instruction],
+    &[&payer, authority],
+  ).await
+}
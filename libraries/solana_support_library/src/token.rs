@@ -0,0 +1,1834 @@
+use solana_program_test::{
+  ProgramTestContext,
+};
+use solana_sdk::{
+  account::{
+    ReadableAccount,
+  },
+  instruction::{
+    Instruction,
+  },
+  program_error::{
+    ProgramError,
+  },
+  pubkey::{
+    Pubkey,
+  },
+  program_pack::{
+    Pack,
+  },
+  signature::{
+    Keypair,
+    Signer,
+  },
+  system_instruction,
+};
+use super::{
+  context::{
+    get_payer,
+    try_get_rent,
+    try_process_transaction,
+    try_simulate_transaction,
+  },
+  error::{
+    FrameworkError,
+    FrameworkResult,
+  },
+  spl_atoken::{
+    create_associated_token_account_idempotent_instruction,
+    create_associated_token_account_idempotent_with_program_id_instruction,
+    create_associated_token_account_with_program_id_instruction,
+    recover_nested_associated_token_account_with_program_id_instruction,
+  },
+  spl_token::{
+    amount_to_ui_amount_instruction,
+    approve_token_checked_instruction,
+    approve_token_instruction,
+    amount_to_ui_amount_string_trimmed,
+    AuthorityType,
+    burn_token_checked_instruction,
+    burn_token_instruction,
+    change_mint_authority_instruction,
+    check_token_program_id,
+    close_token_account_with_destination_instruction,
+    freeze_token_account_instruction,
+    get_account_data_size_instruction,
+    initialize_immutable_owner_instruction,
+    initialize_token_account_instruction,
+    initialize_token_mint_instruction,
+    initialize_token_multisig_instruction,
+    multisig_instruction,
+    mint_token_checked_instruction,
+    mint_token_instruction,
+    ID as TOKEN_PROGRAM_ID,
+    NATIVE_MINT_ID,
+    revoke_token_instruction,
+    set_authority_instruction,
+    sync_native_instruction,
+    thaw_token_account_instruction,
+    transfer_token_checked_instruction,
+    transfer_token_instruction,
+    try_ui_amount_into_amount,
+    TokenAccount,
+    TokenMint,
+    TokenMultisig,
+    TOKEN_ACCOUNT_LENGTH,
+    TOKEN_MINT_LENGTH,
+    TOKEN_MULTISIG_LENGTH,
+    ui_amount_to_amount_instruction,
+  },
+  spl_token_2022::{
+    ACCOUNT_TYPE_OFFSET,
+    ID as TOKEN_2022_PROGRAM_ID,
+    TLV_HEADER_LENGTH,
+    TokenExtension,
+    unpack_token_account,
+    unpack_token_extensions,
+    unpack_token_mint,
+  },
+};
+pub use super::spl_atoken::{
+  get_associated_token_account_address,
+  get_associated_token_account_address_with_program_id,
+};
+
+/// Authority of a token account or mint: a single keypair, or a multisig account
+/// together with the keypairs of its signers.
+#[derive(Clone, Copy)]
+pub enum TokenAuthority<'a> {
+  Signer(&'a Keypair),
+  Multisig(&'a Pubkey, &'a [&'a Keypair]),
+}
+
+impl<'a> TokenAuthority<'a> {
+  pub fn address(&self) -> Pubkey {
+    match self {
+      TokenAuthority::Signer(signer) => signer.pubkey(),
+      TokenAuthority::Multisig(multisig, _) => **multisig,
+    }
+  }
+
+  /// Returns `payer` followed by the keypairs signing for this authority.
+  pub fn signers_with<'b>(&'b self, payer: &'b Keypair) -> Vec<&'b Keypair> {
+    let mut signers = vec![payer];
+    match self {
+      TokenAuthority::Signer(signer) => signers.push(*signer),
+      TokenAuthority::Multisig(_, multisig_signers) => signers.extend_from_slice(multisig_signers),
+    }
+    signers
+  }
+
+  /// Rewrites the authority account of `instruction` for a multisig authority.
+  pub fn apply(&self, instruction: Instruction) -> FrameworkResult<Instruction> {
+    match self {
+      TokenAuthority::Signer(_) => Ok(instruction),
+      TokenAuthority::Multisig(_, multisig_signers) => {
+        let signer_addresses: Vec<Pubkey> = multisig_signers.iter()
+          .map(|signer| signer.pubkey())
+          .collect();
+        let signer_addresses: Vec<&Pubkey> = signer_addresses.iter().collect();
+        let instruction = multisig_instruction(instruction, &signer_addresses)?;
+        Ok(instruction)
+      },
+    }
+  }
+}
+
+impl<'a> From<&'a Keypair> for TokenAuthority<'a> {
+  fn from(signer: &'a Keypair) -> Self {
+    TokenAuthority::Signer(signer)
+  }
+}
+
+/// Balance of a wrapped SOL account.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WrappedSolBalance {
+  /// Token amount, the lamports wrapped as of the last sync
+  pub amount: u64,
+  /// Rent-exempt reserve recorded in `is_native`, not part of the token amount
+  pub rent_exempt_reserve: u64,
+}
+
+/// Token amount as returned by the `getTokenAccountBalance` RPC method.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UiTokenAmount {
+  pub ui_amount: Option<f64>,
+  pub decimals: u8,
+  /// Amount in base units
+  pub amount: String,
+  pub ui_amount_string: String,
+}
+
+pub async fn assert_token_decimals(
+  context: &mut ProgramTestContext,
+  token_mint_address: &Pubkey,
+  expected_decimals: u8,
+) {
+  let token_mint = get_token_mint_info(context, token_mint_address).await;
+  assert_eq!(token_mint.decimals, expected_decimals, "decimals of mint {}", token_mint_address);
+}
+
+pub async fn assert_token_delegate(
+  context: &mut ProgramTestContext,
+  token_account_address: &Pubkey,
+  expected_delegate: Option<&Pubkey>,
+) {
+  let token_account = get_token_account_info(context, token_account_address).await;
+  assert_eq!(
+    Option::<Pubkey>::from(token_account.delegate),
+    expected_delegate.copied(),
+    "delegate of token account {}", token_account_address,
+  );
+}
+
+pub async fn assert_token_delegated_amount(
+  context: &mut ProgramTestContext,
+  token_account_address: &Pubkey,
+  expected_amount: u64,
+) {
+  let token_account = get_token_account_info(context, token_account_address).await;
+  assert_eq!(
+    token_account.delegated_amount,
+    expected_amount,
+    "delegated amount of token account {}", token_account_address,
+  );
+}
+
+pub async fn assert_token_freeze_authority(
+  context: &mut ProgramTestContext,
+  token_mint_address: &Pubkey,
+  expected_authority: Option<&Pubkey>,
+) {
+  let token_mint = get_token_mint_info(context, token_mint_address).await;
+  assert_eq!(
+    Option::<Pubkey>::from(token_mint.freeze_authority),
+    expected_authority.copied(),
+    "freeze authority of mint {}", token_mint_address,
+  );
+}
+
+pub async fn assert_token_mint_authority(
+  context: &mut ProgramTestContext,
+  token_mint_address: &Pubkey,
+  expected_authority: Option<&Pubkey>,
+) {
+  let token_mint = get_token_mint_info(context, token_mint_address).await;
+  assert_eq!(
+    Option::<Pubkey>::from(token_mint.mint_authority),
+    expected_authority.copied(),
+    "mint authority of mint {}", token_mint_address,
+  );
+}
+
+pub async fn assert_token_supply(
+  context: &mut ProgramTestContext,
+  token_mint_address: &Pubkey,
+  expected_supply: u64,
+) {
+  let supply = get_token_supply(context, token_mint_address).await;
+  assert_eq!(supply, expected_supply, "supply of mint {}", token_mint_address);
+}
+
+pub async fn amount_to_ui_amount(
+  context: &mut ProgramTestContext,
+  token_mint_address: &Pubkey,
+  amount: u64,
+) -> String {
+  try_amount_to_ui_amount(context, token_mint_address, amount)
+    .await.unwrap()
+}
+
+/// Converts base units to a UI amount string using the token program, i.e. `1.5` for
+/// `1_500_000` of a 6-decimal mint.
+pub async fn try_amount_to_ui_amount(
+  context: &mut ProgramTestContext,
+  token_mint_address: &Pubkey,
+  amount: u64,
+) -> FrameworkResult<String> {
+  let token_program_id = try_get_token_program_id(context, token_mint_address).await?;
+  let amount_to_ui_amount_ix = amount_to_ui_amount_instruction(
+    &token_program_id,
+    &token_mint_address,
+    amount,
+  );
+
+  let return_data = get_token_return_data(context, amount_to_ui_amount_ix, &token_program_id)
+    .await?;
+  String::from_utf8(return_data)
+    .map_err(|_| FrameworkError::Program(ProgramError::InvalidAccountData))
+}
+
+pub async fn approve_token<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  owner_token_address: &Pubkey,
+  delegate_address: &Pubkey,
+  amount: u64,
+) {
+  try_approve_token(context, owner, owner_token_address, delegate_address, amount)
+    .await.unwrap();
+}
+
+pub async fn try_approve_token<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  owner_token_address: &Pubkey,
+  delegate_address: &Pubkey,
+  amount: u64,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, owner_token_address).await?;
+  let payer = get_payer(context);
+  let owner: TokenAuthority = owner.into();
+
+  let approve_token_ix = approve_token_instruction(
+    &token_program_id,
+    &owner.address(),
+    &owner_token_address,
+    &delegate_address,
+    amount,
+  );
+  let approve_token_ix = owner.apply(approve_token_ix)?;
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[approve_token_ix],
+    &owner.signers_with(&payer),
+  ).await
+}
+
+pub async fn approve_token_checked<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  owner_token_address: &Pubkey,
+  token_mint_address: &Pubkey,
+  delegate_address: &Pubkey,
+  amount: u64,
+  decimals: u8,
+) {
+  try_approve_token_checked(context, owner, owner_token_address, token_mint_address, delegate_address, amount, decimals)
+    .await.unwrap();
+}
+
+pub async fn try_approve_token_checked<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  owner_token_address: &Pubkey,
+  token_mint_address: &Pubkey,
+  delegate_address: &Pubkey,
+  amount: u64,
+  decimals: u8,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, owner_token_address).await?;
+  let payer = get_payer(context);
+  let owner: TokenAuthority = owner.into();
+
+  let approve_token_ix = approve_token_checked_instruction(
+    &token_program_id,
+    &owner.address(),
+    &owner_token_address,
+    &token_mint_address,
+    &delegate_address,
+    amount,
+    decimals,
+  );
+  let approve_token_ix = owner.apply(approve_token_ix)?;
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[approve_token_ix],
+    &owner.signers_with(&payer),
+  ).await
+}
+
+pub async fn burn_token<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  owner_token_address: &Pubkey,
+  token_mint_address: &Pubkey,
+  amount: u64,
+) {
+  try_burn_token(context, owner, owner_token_address, token_mint_address, amount)
+    .await.unwrap();
+}
+
+/// `owner` may also be the delegate of the token account, spending the allowance granted
+/// by `approve_token`.
+pub async fn try_burn_token<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  owner_token_address: &Pubkey,
+  token_mint_address: &Pubkey,
+  amount: u64,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, owner_token_address).await?;
+  let payer = get_payer(context);
+  let owner: TokenAuthority = owner.into();
+
+  let burn_token_ix = burn_token_instruction(
+    &token_program_id,
+    &owner.address(),
+    &owner_token_address,
+    &token_mint_address,
+    amount,
+  );
+  let burn_token_ix = owner.apply(burn_token_ix)?;
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[burn_token_ix],
+    &owner.signers_with(&payer),
+  ).await
+}
+
+pub async fn burn_token_checked<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  owner_token_address: &Pubkey,
+  token_mint_address: &Pubkey,
+  amount: u64,
+  decimals: u8,
+) {
+  try_burn_token_checked(context, owner, owner_token_address, token_mint_address, amount, decimals)
+    .await.unwrap();
+}
+
+pub async fn try_burn_token_checked<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  owner_token_address: &Pubkey,
+  token_mint_address: &Pubkey,
+  amount: u64,
+  decimals: u8,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, owner_token_address).await?;
+  let payer = get_payer(context);
+  let owner: TokenAuthority = owner.into();
+
+  let burn_token_ix = burn_token_checked_instruction(
+    &token_program_id,
+    &owner.address(),
+    &owner_token_address,
+    &token_mint_address,
+    amount,
+    decimals,
+  );
+  let burn_token_ix = owner.apply(burn_token_ix)?;
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[burn_token_ix],
+    &owner.signers_with(&payer),
+  ).await
+}
+
+pub async fn burn_token_ui<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  owner_token_address: &Pubkey,
+  token_mint_address: &Pubkey,
+  ui_amount: &str,
+) {
+  try_burn_token_ui(context, owner, owner_token_address, token_mint_address, ui_amount)
+    .await.unwrap();
+}
+
+/// Burns a UI amount, i.e. `"1.5"`, using the decimals of the mint and BurnChecked.
+pub async fn try_burn_token_ui<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  owner_token_address: &Pubkey,
+  token_mint_address: &Pubkey,
+  ui_amount: &str,
+) -> FrameworkResult<()> {
+  let decimals = try_get_token_mint_info(context, token_mint_address).await?.decimals;
+  let amount = try_ui_amount_into_amount(ui_amount, decimals)?;
+  try_burn_token_checked(context, owner, owner_token_address, token_mint_address, amount, decimals)
+    .await
+}
+
+pub async fn change_mint_authority<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_mint_address: &Pubkey,
+  auhtority_type: AuthorityType,
+  new_authority_address: Option<&Pubkey>,
+) {
+  try_change_mint_authority(context, authority, token_mint_address, auhtority_type, new_authority_address)
+    .await.unwrap();
+}
+
+pub async fn try_change_mint_authority<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_mint_address: &Pubkey,
+  auhtority_type: AuthorityType,
+  new_authority_address: Option<&Pubkey>,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, token_mint_address).await?;
+  let payer = get_payer(context);
+  let authority: TokenAuthority = authority.into();
+
+  let change_authority_ix = change_mint_authority_instruction(
+    &token_program_id,
+    &authority.address(),
+    &token_mint_address,
+    auhtority_type,
+    new_authority_address,
+  );
+  let change_authority_ix = authority.apply(change_authority_ix)?;
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[change_authority_ix],
+    &authority.signers_with(&payer),
+  ).await
+}
+
+pub async fn close_token_account<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  owner_token_address: &Pubkey,
+) {
+  try_close_token_account(context, owner, owner_token_address)
+    .await.unwrap();
+}
+
+pub async fn try_close_token_account<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  owner_token_address: &Pubkey,
+) -> FrameworkResult<()> {
+  let owner: TokenAuthority = owner.into();
+  let owner_address = owner.address();
+  try_close_token_account_with_destination(context, owner, owner_token_address, &owner_address)
+    .await
+}
+
+pub async fn close_token_account_with_destination<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_account_address: &Pubkey,
+  destination_address: &Pubkey,
+) {
+  try_close_token_account_with_destination(context, authority, token_account_address, destination_address)
+    .await.unwrap();
+}
+
+/// Closes a token account signed by its owner or close authority, sending its lamports
+/// to `destination_address`.
+pub async fn try_close_token_account_with_destination<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_account_address: &Pubkey,
+  destination_address: &Pubkey,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, token_account_address).await?;
+  let payer = get_payer(context);
+  let authority: TokenAuthority = authority.into();
+
+  let close_token_account_ix = close_token_account_with_destination_instruction(
+    &token_program_id,
+    &authority.address(),
+    &token_account_address,
+    &destination_address,
+  );
+  let close_token_account_ix = authority.apply(close_token_account_ix)?;
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[close_token_account_ix],
+    &authority.signers_with(&payer),
+  ).await
+}
+
+pub async fn create_token_account(
+  context: &mut ProgramTestContext,
+  wallet_address:&Pubkey,
+  token_mint_address: &Pubkey,
+  token_account: &Keypair,
+) {
+  try_create_token_account(context, wallet_address, token_mint_address, token_account)
+    .await.unwrap();
+}
+
+pub async fn try_create_token_account(
+  context: &mut ProgramTestContext,
+  wallet_address:&Pubkey,
+  token_mint_address: &Pubkey,
+  token_account: &Keypair,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, token_mint_address).await?;
+  let payer = get_payer(context);
+  let rent = try_get_rent(context).await?;
+  let account_length = try_get_token_account_data_size(context, token_mint_address)
+    .await? as usize;
+
+  let create_account_ix = system_instruction::create_account(
+    &payer.pubkey(),
+    &token_account.pubkey(),
+    rent.minimum_balance(account_length),
+    account_length as u64,
+    &token_program_id,
+  );
+  let initialize_account_ix = initialize_token_account_instruction(
+    &token_program_id,
+    &wallet_address,
+    &token_account.pubkey(),
+    &token_mint_address,
+  );
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[create_account_ix, initialize_account_ix],
+    &[&payer, &token_account],
+  ).await
+}
+
+pub async fn create_token_account_with_immutable_owner(
+  context: &mut ProgramTestContext,
+  wallet_address:&Pubkey,
+  token_mint_address: &Pubkey,
+  token_account: &Keypair,
+) {
+  try_create_token_account_with_immutable_owner(context, wallet_address, token_mint_address, token_account)
+    .await.unwrap();
+}
+
+pub async fn try_create_token_account_with_immutable_owner(
+  context: &mut ProgramTestContext,
+  wallet_address:&Pubkey,
+  token_mint_address: &Pubkey,
+  token_account: &Keypair,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, token_mint_address).await?;
+  let payer = get_payer(context);
+  let rent = try_get_rent(context).await?;
+  let mut account_length = try_get_token_account_data_size(context, token_mint_address)
+    .await? as usize;
+  if token_program_id == TOKEN_2022_PROGRAM_ID {
+    // the ImmutableOwner extension is an empty TLV entry after the account type byte
+    if account_length == TOKEN_ACCOUNT_LENGTH {
+      account_length += 1;
+    }
+    account_length += 4;
+  }
+
+  let create_account_ix = system_instruction::create_account(
+    &payer.pubkey(),
+    &token_account.pubkey(),
+    rent.minimum_balance(account_length),
+    account_length as u64,
+    &token_program_id,
+  );
+  let initialize_immutable_owner_ix = initialize_immutable_owner_instruction(
+    &token_program_id,
+    &token_account.pubkey(),
+  );
+  let initialize_account_ix = initialize_token_account_instruction(
+    &token_program_id,
+    &wallet_address,
+    &token_account.pubkey(),
+    &token_mint_address,
+  );
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[create_account_ix, initialize_immutable_owner_ix, initialize_account_ix],
+    &[&payer, &token_account],
+  ).await
+}
+
+pub async fn create_token_mint(
+  context: &mut ProgramTestContext,
+  token_mint: &Keypair,
+  decimals: u8,
+  authority: &Pubkey,
+  freeze_authority: Option<&Pubkey>,
+) {
+  try_create_token_mint(context, token_mint, decimals, authority, freeze_authority)
+    .await.unwrap();
+}
+
+pub async fn try_create_token_mint(
+  context: &mut ProgramTestContext,
+  token_mint: &Keypair,
+  decimals: u8,
+  authority: &Pubkey,
+  freeze_authority: Option<&Pubkey>,
+) -> FrameworkResult<()> {
+  try_create_token_mint_with_program_id(context, token_mint, decimals, authority, freeze_authority, &TOKEN_PROGRAM_ID)
+    .await
+}
+
+pub async fn create_token_mint_with_program_id(
+  context: &mut ProgramTestContext,
+  token_mint: &Keypair,
+  decimals: u8,
+  authority: &Pubkey,
+  freeze_authority: Option<&Pubkey>,
+  token_program_id: &Pubkey,
+) {
+  try_create_token_mint_with_program_id(context, token_mint, decimals, authority, freeze_authority, token_program_id)
+    .await.unwrap();
+}
+
+/// Creates a mint without extensions owned by `token_program_id`, SPL Token or Token-2022.
+pub async fn try_create_token_mint_with_program_id(
+  context: &mut ProgramTestContext,
+  token_mint: &Keypair,
+  decimals: u8,
+  authority: &Pubkey,
+  freeze_authority: Option<&Pubkey>,
+  token_program_id: &Pubkey,
+) -> FrameworkResult<()> {
+  check_token_program_id(token_program_id)?;
+  let payer = get_payer(context);
+  let rent = try_get_rent(context).await?;
+
+  let create_account_ix = system_instruction::create_account(
+    &payer.pubkey(),
+    &token_mint.pubkey(),
+    rent.minimum_balance(TOKEN_MINT_LENGTH),
+    TOKEN_MINT_LENGTH as u64,
+    token_program_id,
+  );
+  let initialize_mint_ix = initialize_token_mint_instruction(
+    token_program_id,
+    &token_mint.pubkey(),
+    decimals,
+    &authority,
+    freeze_authority,
+  );
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[create_account_ix, initialize_mint_ix],
+    &[&payer, &token_mint],
+  ).await
+}
+
+pub async fn create_token_multisig(
+  context: &mut ProgramTestContext,
+  multisig: &Keypair,
+  signer_addresses: &[&Pubkey],
+  m: u8,
+) {
+  try_create_token_multisig(context, multisig, signer_addresses, m)
+    .await.unwrap();
+}
+
+pub async fn try_create_token_multisig(
+  context: &mut ProgramTestContext,
+  multisig: &Keypair,
+  signer_addresses: &[&Pubkey],
+  m: u8,
+) -> FrameworkResult<()> {
+  try_create_token_multisig_with_program_id(context, multisig, signer_addresses, m, &TOKEN_PROGRAM_ID)
+    .await
+}
+
+pub async fn create_token_multisig_with_program_id(
+  context: &mut ProgramTestContext,
+  multisig: &Keypair,
+  signer_addresses: &[&Pubkey],
+  m: u8,
+  token_program_id: &Pubkey,
+) {
+  try_create_token_multisig_with_program_id(context, multisig, signer_addresses, m, token_program_id)
+    .await.unwrap();
+}
+
+/// Creates an M-of-N multisig owned by `token_program_id`, SPL Token or Token-2022.
+pub async fn try_create_token_multisig_with_program_id(
+  context: &mut ProgramTestContext,
+  multisig: &Keypair,
+  signer_addresses: &[&Pubkey],
+  m: u8,
+  token_program_id: &Pubkey,
+) -> FrameworkResult<()> {
+  check_token_program_id(token_program_id)?;
+  let payer = get_payer(context);
+  let rent = try_get_rent(context).await?;
+
+  let create_account_ix = system_instruction::create_account(
+    &payer.pubkey(),
+    &multisig.pubkey(),
+    rent.minimum_balance(TOKEN_MULTISIG_LENGTH),
+    TOKEN_MULTISIG_LENGTH as u64,
+    token_program_id,
+  );
+  let initialize_multisig_ix = initialize_token_multisig_instruction(
+    token_program_id,
+    &multisig.pubkey(),
+    signer_addresses,
+    m,
+  );
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[create_account_ix, initialize_multisig_ix],
+    &[&payer, &multisig],
+  ).await
+}
+
+pub async fn create_associated_token_account(
+  context: &mut ProgramTestContext,
+  owner: &Pubkey,
+  token_mint: &Pubkey,
+) -> Pubkey {
+  try_create_associated_token_account(context, owner, token_mint)
+    .await.unwrap()
+}
+
+pub async fn try_create_associated_token_account(
+  context: &mut ProgramTestContext,
+  owner: &Pubkey,
+  token_mint: &Pubkey,
+) -> FrameworkResult<Pubkey> {
+  let token_program_id = try_get_token_program_id(context, token_mint).await?;
+  let payer = get_payer(context);
+
+  let create_ata_ix = create_associated_token_account_with_program_id_instruction(
+    &payer.pubkey(),
+    &owner,
+    token_mint,
+    &token_program_id,
+  );
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[create_ata_ix],
+    &[&payer]
+  ).await?;
+
+  Ok(get_associated_token_account_address_with_program_id(
+    owner,
+    token_mint,
+    &token_program_id,
+  ))
+}
+
+pub async fn create_associated_token_account_idempotent(
+  context: &mut ProgramTestContext,
+  owner: &Pubkey,
+  token_mint: &Pubkey,
+) -> Pubkey {
+  try_create_associated_token_account_idempotent(context, owner, token_mint)
+    .await.unwrap()
+}
+
+/// Creates the associated token account of `owner` unless it already exists.
+pub async fn try_create_associated_token_account_idempotent(
+  context: &mut ProgramTestContext,
+  owner: &Pubkey,
+  token_mint: &Pubkey,
+) -> FrameworkResult<Pubkey> {
+  let token_program_id = try_get_token_program_id(context, token_mint).await?;
+  let payer = get_payer(context);
+
+  let create_ata_ix = create_associated_token_account_idempotent_with_program_id_instruction(
+    &payer.pubkey(),
+    &owner,
+    token_mint,
+    &token_program_id,
+  );
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[create_ata_ix],
+    &[&payer]
+  ).await?;
+
+  Ok(get_associated_token_account_address_with_program_id(
+    owner,
+    token_mint,
+    &token_program_id,
+  ))
+}
+
+pub async fn freeze_token_account<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_mint_address: &Pubkey,
+  token_account_address: &Pubkey,
+) {
+  try_freeze_token_account(context, authority, token_mint_address, token_account_address)
+    .await.unwrap();
+}
+
+pub async fn try_freeze_token_account<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_mint_address: &Pubkey,
+  token_account_address: &Pubkey,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, token_account_address).await?;
+  let payer = get_payer(context);
+  let authority: TokenAuthority = authority.into();
+
+  let freeze_token_account_ix = freeze_token_account_instruction(
+    &token_program_id,
+    &authority.address(),
+    &token_mint_address,
+    &token_account_address,
+  );
+  let freeze_token_account_ix = authority.apply(freeze_token_account_ix)?;
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[freeze_token_account_ix],
+    &authority.signers_with(&payer),
+  ).await
+}
+
+pub async fn get_token_account_balance(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> u64 {
+  try_get_token_account_balance(context, address)
+    .await.unwrap()
+}
+
+pub async fn try_get_token_account_balance(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> FrameworkResult<u64> {
+  let token_account = try_get_token_account_info(context, address)
+    .await?;
+  Ok(token_account.amount)
+}
+
+pub async fn get_token_account_data_size(
+  context: &mut ProgramTestContext,
+  token_mint_address: &Pubkey,
+) -> u64 {
+  try_get_token_account_data_size(context, token_mint_address)
+    .await.unwrap()
+}
+
+/// Returns the size of a token account for the mint. Computed locally for SPL Token mints and
+/// Token-2022 mints with known extensions, asked to the token program otherwise.
+pub async fn try_get_token_account_data_size(
+  context: &mut ProgramTestContext,
+  token_mint_address: &Pubkey,
+) -> FrameworkResult<u64> {
+  let token_mint_info = context.banks_client
+    .get_account(*token_mint_address)
+    .await?
+    .ok_or(FrameworkError::AccountNotFound(*token_mint_address))?;
+  let token_program_id = token_mint_info.owner;
+  check_token_program_id(&token_program_id)?;
+  if let Some(account_length) = get_token_account_length(&token_program_id, &token_mint_info.data) {
+    return Ok(account_length as u64);
+  }
+
+  let get_account_data_size_ix = get_account_data_size_instruction(
+    &token_program_id,
+    &token_mint_address,
+  );
+
+  let return_data = get_token_return_data(context, get_account_data_size_ix, &token_program_id)
+    .await?;
+  unpack_u64(&return_data)
+}
+
+pub async fn get_token_account_info(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> TokenAccount {
+  try_get_token_account_info(context, address)
+    .await.unwrap()
+}
+
+pub async fn try_get_token_account_info(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> FrameworkResult<TokenAccount> {
+  let token_account_option = context.banks_client
+    .get_account(*address)
+    .await?;
+  let token_account_info = token_account_option
+    .ok_or(FrameworkError::AccountNotFound(*address))?;
+  let token_account = unpack_token_account(token_account_info.data())?;
+  Ok(token_account)
+}
+
+pub async fn get_token_account_mint(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> Pubkey {
+  try_get_token_account_mint(context, address)
+    .await.unwrap()
+}
+
+pub async fn try_get_token_account_mint(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> FrameworkResult<Pubkey> {
+  let token_account = try_get_token_account_info(context, address)
+    .await?;
+  Ok(token_account.mint)
+}
+
+pub async fn get_token_account_ui_balance(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> UiTokenAmount {
+  try_get_token_account_ui_balance(context, address)
+    .await.unwrap()
+}
+
+/// Returns the balance of a token account formatted like `getTokenAccountBalance` over RPC.
+pub async fn try_get_token_account_ui_balance(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> FrameworkResult<UiTokenAmount> {
+  let token_account = try_get_token_account_info(context, address)
+    .await?;
+  let decimals = try_get_token_mint_info(context, &token_account.mint)
+    .await?
+    .decimals;
+  Ok(UiTokenAmount {
+    ui_amount: Some(token_account.amount as f64 / 10f64.powi(decimals as i32)),
+    decimals,
+    amount: token_account.amount.to_string(),
+    ui_amount_string: amount_to_ui_amount_string_trimmed(token_account.amount, decimals),
+  })
+}
+
+pub async fn get_token_extensions(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> Vec<TokenExtension> {
+  try_get_token_extensions(context, address)
+    .await.unwrap()
+}
+
+/// Returns the Token-2022 extensions of a mint or token account.
+pub async fn try_get_token_extensions(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> FrameworkResult<Vec<TokenExtension>> {
+  let account_option = context.banks_client
+    .get_account(*address)
+    .await?;
+  let account_info = account_option
+    .ok_or(FrameworkError::AccountNotFound(*address))?;
+  let extensions = unpack_token_extensions(account_info.data())?;
+  Ok(extensions)
+}
+
+pub async fn get_token_mint_info(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> TokenMint {
+  try_get_token_mint_info(context, address)
+    .await.unwrap()
+}
+
+pub async fn try_get_token_mint_info(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> FrameworkResult<TokenMint> {
+  let token_mint_option = context.banks_client
+    .get_account(*address)
+    .await?;
+  let token_mint_info = token_mint_option
+    .ok_or(FrameworkError::AccountNotFound(*address))?;
+  let token_mint = unpack_token_mint(token_mint_info.data())?;
+  Ok(token_mint)
+}
+
+pub async fn get_token_multisig_info(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> TokenMultisig {
+  try_get_token_multisig_info(context, address)
+    .await.unwrap()
+}
+
+pub async fn try_get_token_multisig_info(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> FrameworkResult<TokenMultisig> {
+  let multisig_account_option = context.banks_client
+    .get_account(*address)
+    .await?;
+  let multisig_account_info = multisig_account_option
+    .ok_or(FrameworkError::AccountNotFound(*address))?;
+  let multisig = TokenMultisig::unpack(multisig_account_info.data())?;
+  Ok(multisig)
+}
+
+pub async fn get_token_program_id(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> Pubkey {
+  try_get_token_program_id(context, address)
+    .await.unwrap()
+}
+
+/// Returns the token program owning a mint or token account, SPL Token or Token-2022.
+pub async fn try_get_token_program_id(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> FrameworkResult<Pubkey> {
+  let account_option = context.banks_client
+    .get_account(*address)
+    .await?;
+  let account_info = account_option
+    .ok_or(FrameworkError::AccountNotFound(*address))?;
+  check_token_program_id(&account_info.owner)?;
+  Ok(account_info.owner)
+}
+
+pub async fn get_token_supply(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> u64 {
+  try_get_token_supply(context, address)
+    .await.unwrap()
+}
+
+pub async fn try_get_token_supply(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> FrameworkResult<u64> {
+  let token_mint = try_get_token_mint_info(context, address)
+    .await?;
+  Ok(token_mint.supply)
+}
+
+pub async fn get_wrapped_sol_balance(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> WrappedSolBalance {
+  try_get_wrapped_sol_balance(context, address)
+    .await.unwrap()
+}
+
+pub async fn try_get_wrapped_sol_balance(
+  context: &mut ProgramTestContext,
+  address: &Pubkey,
+) -> FrameworkResult<WrappedSolBalance> {
+  let token_account = try_get_token_account_info(context, address)
+    .await?;
+  let rent_exempt_reserve = token_account.is_native
+    .ok_or(FrameworkError::Program(ProgramError::InvalidAccountData))?;
+  Ok(WrappedSolBalance {
+    amount: token_account.amount,
+    rent_exempt_reserve,
+  })
+}
+
+pub async fn mint_token<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_mint: &Pubkey,
+  recipient: &Pubkey,
+  amount: u64,
+) {
+  try_mint_token(context, authority, token_mint, recipient, amount)
+    .await.unwrap();
+}
+
+pub async fn try_mint_token<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_mint: &Pubkey,
+  recipient: &Pubkey,
+  amount: u64,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, token_mint).await?;
+  let payer = get_payer(context);
+  let authority: TokenAuthority = authority.into();
+
+  let mut instructions: Vec<Instruction> = Vec::new();
+  let (recipient_address, create_ata_ix_option) = check_and_create_ata_ix(
+      context,
+      recipient,
+      &payer,
+      token_mint,
+      &token_program_id,
+    ).await?;
+  if create_ata_ix_option.is_some() {
+    instructions.push(create_ata_ix_option.unwrap());
+  }
+
+  let mint_token_ix = mint_token_instruction(
+    &token_program_id,
+    &authority.address(),
+    &token_mint,
+    &recipient_address,
+    amount,
+  );
+  let mint_token_ix = authority.apply(mint_token_ix)?;
+  instructions.push(mint_token_ix);
+
+  try_process_transaction(
+      context,
+      &payer,
+      &instructions,
+      &authority.signers_with(&payer),
+    ).await
+}
+
+pub async fn mint_token_checked<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_mint: &Pubkey,
+  recipient: &Pubkey,
+  amount: u64,
+  decimals: u8,
+) {
+  try_mint_token_checked(context, authority, token_mint, recipient, amount, decimals)
+    .await.unwrap();
+}
+
+pub async fn try_mint_token_checked<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_mint: &Pubkey,
+  recipient: &Pubkey,
+  amount: u64,
+  decimals: u8,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, token_mint).await?;
+  let payer = get_payer(context);
+  let authority: TokenAuthority = authority.into();
+
+  let mut instructions: Vec<Instruction> = Vec::new();
+  let (recipient_address, create_ata_ix_option) = check_and_create_ata_ix(
+    context,
+    recipient,
+    &payer,
+    token_mint,
+    &token_program_id,
+  ).await?;
+  if create_ata_ix_option.is_some() {
+    instructions.push(create_ata_ix_option.unwrap());
+  }
+
+  let mint_token_ix = mint_token_checked_instruction(
+    &token_program_id,
+    &authority.address(),
+    &token_mint,
+    &recipient_address,
+    amount,
+    decimals,
+  );
+  let mint_token_ix = authority.apply(mint_token_ix)?;
+  instructions.push(mint_token_ix);
+
+  try_process_transaction(
+    context,
+    &payer,
+    &instructions,
+    &authority.signers_with(&payer),
+  ).await
+}
+
+pub async fn mint_token_ui<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_mint: &Pubkey,
+  recipient: &Pubkey,
+  ui_amount: &str,
+) {
+  try_mint_token_ui(context, authority, token_mint, recipient, ui_amount)
+    .await.unwrap();
+}
+
+/// Mints a UI amount, i.e. `"1.5"`, using the decimals of the mint and MintToChecked.
+pub async fn try_mint_token_ui<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_mint: &Pubkey,
+  recipient: &Pubkey,
+  ui_amount: &str,
+) -> FrameworkResult<()> {
+  let decimals = try_get_token_mint_info(context, token_mint).await?.decimals;
+  let amount = try_ui_amount_into_amount(ui_amount, decimals)?;
+  try_mint_token_checked(context, authority, token_mint, recipient, amount, decimals)
+    .await
+}
+
+pub async fn recover_nested_associated_token_account(
+  context: &mut ProgramTestContext,
+  wallet: &Keypair,
+  owner_token_mint: &Pubkey,
+  nested_token_mint: &Pubkey,
+) {
+  try_recover_nested_associated_token_account(context, wallet, owner_token_mint, nested_token_mint)
+    .await.unwrap();
+}
+
+/// Moves the tokens of the `nested_token_mint` associated token account owned by the
+/// `owner_token_mint` associated token account of `wallet` back to the wallet, and closes it.
+pub async fn try_recover_nested_associated_token_account(
+  context: &mut ProgramTestContext,
+  wallet: &Keypair,
+  owner_token_mint: &Pubkey,
+  nested_token_mint: &Pubkey,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, nested_token_mint).await?;
+  let payer = get_payer(context);
+
+  let recover_nested_ix = recover_nested_associated_token_account_with_program_id_instruction(
+    &wallet.pubkey(),
+    owner_token_mint,
+    nested_token_mint,
+    &token_program_id,
+  );
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[recover_nested_ix],
+    &[&payer, &wallet],
+  ).await
+}
+
+pub async fn revoke_token<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  owner_token_address: &Pubkey,
+) {
+  try_revoke_token(context, owner, owner_token_address)
+    .await.unwrap();
+}
+
+pub async fn try_revoke_token<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  owner_token_address: &Pubkey,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, owner_token_address).await?;
+  let payer = get_payer(context);
+  let owner: TokenAuthority = owner.into();
+
+  let revoke_token_ix = revoke_token_instruction(
+    &token_program_id,
+    &owner.address(),
+    &owner_token_address,
+  );
+  let revoke_token_ix = owner.apply(revoke_token_ix)?;
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[revoke_token_ix],
+    &owner.signers_with(&payer),
+  ).await
+}
+
+pub async fn set_close_authority<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_account_address: &Pubkey,
+  new_close_authority_address: Option<&Pubkey>,
+) {
+  try_set_close_authority(context, authority, token_account_address, new_close_authority_address)
+    .await.unwrap();
+}
+
+/// Sets or clears the close authority of a token account, signed by its owner or current
+/// close authority.
+pub async fn try_set_close_authority<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_account_address: &Pubkey,
+  new_close_authority_address: Option<&Pubkey>,
+) -> FrameworkResult<()> {
+  try_set_token_account_authority(context, authority, token_account_address, AuthorityType::CloseAccount, new_close_authority_address)
+    .await
+}
+
+pub async fn set_token_account_owner<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  token_account_address: &Pubkey,
+  new_owner_address: &Pubkey,
+) {
+  try_set_token_account_owner(context, owner, token_account_address, new_owner_address)
+    .await.unwrap();
+}
+
+/// Transfers ownership of a token account. Fails for accounts with an immutable owner,
+/// such as Token-2022 associated token accounts.
+pub async fn try_set_token_account_owner<'a>(
+  context: &mut ProgramTestContext,
+  owner: impl Into<TokenAuthority<'a>>,
+  token_account_address: &Pubkey,
+  new_owner_address: &Pubkey,
+) -> FrameworkResult<()> {
+  try_set_token_account_authority(context, owner, token_account_address, AuthorityType::AccountOwner, Some(new_owner_address))
+    .await
+}
+
+pub async fn sync_native(
+  context: &mut ProgramTestContext,
+  token_account_address: &Pubkey,
+) {
+  try_sync_native(context, token_account_address)
+    .await.unwrap();
+}
+
+pub async fn try_sync_native(
+  context: &mut ProgramTestContext,
+  token_account_address: &Pubkey,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, token_account_address).await?;
+  let payer = get_payer(context);
+
+  let sync_native_ix = sync_native_instruction(
+    &token_program_id,
+    &token_account_address,
+  );
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[sync_native_ix],
+    &[&payer],
+  ).await
+}
+
+pub async fn thaw_token_account<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_mint_address: &Pubkey,
+  token_account_address: &Pubkey,
+) {
+  try_thaw_token_account(context, authority, token_mint_address, token_account_address)
+    .await.unwrap();
+}
+
+pub async fn try_thaw_token_account<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_mint_address: &Pubkey,
+  token_account_address: &Pubkey,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, token_account_address).await?;
+  let payer = get_payer(context);
+  let authority: TokenAuthority = authority.into();
+
+  let thaw_token_account_ix = thaw_token_account_instruction(
+    &token_program_id,
+    &authority.address(),
+    &token_mint_address,
+    &token_account_address,
+  );
+  let thaw_token_account_ix = authority.apply(thaw_token_account_ix)?;
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[thaw_token_account_ix],
+    &authority.signers_with(&payer),
+  ).await
+}
+
+pub async fn top_up_wrapped_sol(
+  context: &mut ProgramTestContext,
+  sender: &Keypair,
+  token_account_address: &Pubkey,
+  lamports: u64,
+) {
+  try_top_up_wrapped_sol(context, sender, token_account_address, lamports)
+    .await.unwrap();
+}
+
+/// Transfers `lamports` from `sender` into an existing wrapped SOL account and syncs its amount.
+pub async fn try_top_up_wrapped_sol(
+  context: &mut ProgramTestContext,
+  sender: &Keypair,
+  token_account_address: &Pubkey,
+  lamports: u64,
+) -> FrameworkResult<()> {
+  let payer = get_payer(context);
+
+  let transfer_ix = system_instruction::transfer(
+    &sender.pubkey(),
+    &token_account_address,
+    lamports,
+  );
+  let sync_native_ix = sync_native_instruction(
+    &TOKEN_PROGRAM_ID,
+    &token_account_address,
+  );
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[transfer_ix, sync_native_ix],
+    &[&payer, &sender],
+  ).await
+}
+
+pub async fn transfer_token<'a>(
+  context: &mut ProgramTestContext,
+  sender: impl Into<TokenAuthority<'a>>,
+  sender_token: &Pubkey,
+  recipient: &Pubkey,
+  amount: u64,
+) {
+  try_transfer_token(context, sender, sender_token, recipient, amount)
+    .await.unwrap();
+}
+
+/// `sender` may also be the delegate of the token account, spending the allowance granted
+/// by `approve_token`.
+pub async fn try_transfer_token<'a>(
+  context: &mut ProgramTestContext,
+  sender: impl Into<TokenAuthority<'a>>,
+  sender_token: &Pubkey,
+  recipient: &Pubkey,
+  amount: u64,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, sender_token).await?;
+  let payer = get_payer(context);
+  let sender: TokenAuthority = sender.into();
+
+  let mut instructions: Vec<Instruction> = Vec::new();
+  let token_mint = try_get_token_account_mint(context, sender_token).await?;
+  let (recipient_address, create_ata_ix_option) = check_and_create_ata_ix(
+    context,
+    recipient,
+    &payer,
+    &token_mint,
+    &token_program_id,
+  ).await?;
+  if create_ata_ix_option.is_some() {
+    instructions.push(create_ata_ix_option.unwrap());
+  }
+
+  let transfer_token_ix = transfer_token_instruction(
+    &token_program_id,
+    &sender.address(),
+    &sender_token,
+    &recipient_address,
+    amount,
+  );
+  let transfer_token_ix = sender.apply(transfer_token_ix)?;
+  instructions.push(transfer_token_ix);
+
+  try_process_transaction(
+    context,
+    &payer,
+    &instructions,
+    &sender.signers_with(&payer),
+  ).await
+}
+
+pub async fn transfer_token_checked<'a>(
+  context: &mut ProgramTestContext,
+  sender: impl Into<TokenAuthority<'a>>,
+  sender_token: &Pubkey,
+  recipient: &Pubkey,
+  amount: u64,
+  decimals: u8,
+) {
+  try_transfer_token_checked(context, sender, sender_token, recipient, amount, decimals)
+    .await.unwrap();
+}
+
+pub async fn try_transfer_token_checked<'a>(
+  context: &mut ProgramTestContext,
+  sender: impl Into<TokenAuthority<'a>>,
+  sender_token: &Pubkey,
+  recipient: &Pubkey,
+  amount: u64,
+  decimals: u8,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, sender_token).await?;
+  let payer = get_payer(context);
+  let sender: TokenAuthority = sender.into();
+
+  let mut instructions: Vec<Instruction> = Vec::new();
+  let token_mint = try_get_token_account_mint(context, sender_token).await?;
+  let (recipient_address, create_ata_ix_option) = check_and_create_ata_ix(
+    context,
+    recipient,
+    &payer,
+    &token_mint,
+    &token_program_id,
+  ).await?;
+  if create_ata_ix_option.is_some() {
+    instructions.push(create_ata_ix_option.unwrap());
+  }
+
+  let transfer_token_ix = transfer_token_checked_instruction(
+    &token_program_id,
+    &sender.address(),
+    &sender_token,
+    &token_mint,
+    &recipient_address,
+    amount,
+    decimals,
+  );
+  let transfer_token_ix = sender.apply(transfer_token_ix)?;
+  instructions.push(transfer_token_ix);
+
+  try_process_transaction(
+    context,
+    &payer,
+    &instructions,
+    &sender.signers_with(&payer),
+  ).await
+}
+
+pub async fn transfer_token_ui<'a>(
+  context: &mut ProgramTestContext,
+  sender: impl Into<TokenAuthority<'a>>,
+  sender_token: &Pubkey,
+  recipient: &Pubkey,
+  ui_amount: &str,
+) {
+  try_transfer_token_ui(context, sender, sender_token, recipient, ui_amount)
+    .await.unwrap();
+}
+
+/// Transfers a UI amount, i.e. `"1.5"`, using the decimals of the mint and TransferChecked.
+pub async fn try_transfer_token_ui<'a>(
+  context: &mut ProgramTestContext,
+  sender: impl Into<TokenAuthority<'a>>,
+  sender_token: &Pubkey,
+  recipient: &Pubkey,
+  ui_amount: &str,
+) -> FrameworkResult<()> {
+  let token_mint = try_get_token_account_mint(context, sender_token).await?;
+  let decimals = try_get_token_mint_info(context, &token_mint).await?.decimals;
+  let amount = try_ui_amount_into_amount(ui_amount, decimals)?;
+  try_transfer_token_checked(context, sender, sender_token, recipient, amount, decimals)
+    .await
+}
+
+pub async fn ui_amount_to_amount(
+  context: &mut ProgramTestContext,
+  token_mint_address: &Pubkey,
+  ui_amount: &str,
+) -> u64 {
+  try_ui_amount_to_amount(context, token_mint_address, ui_amount)
+    .await.unwrap()
+}
+
+/// Converts a UI amount string to base units using the token program.
+pub async fn try_ui_amount_to_amount(
+  context: &mut ProgramTestContext,
+  token_mint_address: &Pubkey,
+  ui_amount: &str,
+) -> FrameworkResult<u64> {
+  let token_program_id = try_get_token_program_id(context, token_mint_address).await?;
+  let ui_amount_to_amount_ix = ui_amount_to_amount_instruction(
+    &token_program_id,
+    &token_mint_address,
+    ui_amount,
+  );
+
+  let return_data = get_token_return_data(context, ui_amount_to_amount_ix, &token_program_id)
+    .await?;
+  unpack_u64(&return_data)
+}
+
+pub async fn unwrap_sol(
+  context: &mut ProgramTestContext,
+  owner: &Keypair,
+) {
+  try_unwrap_sol(context, owner)
+    .await.unwrap();
+}
+
+/// Closes the wrapped SOL associated token account of `owner`, returning all its lamports
+/// to `owner`.
+pub async fn try_unwrap_sol(
+  context: &mut ProgramTestContext,
+  owner: &Keypair,
+) -> FrameworkResult<()> {
+  let token_account_address = get_associated_token_account_address(
+    &owner.pubkey(),
+    &NATIVE_MINT_ID,
+  );
+  try_close_token_account(context, owner, &token_account_address)
+    .await
+}
+
+pub async fn wrap_sol(
+  context: &mut ProgramTestContext,
+  owner: &Keypair,
+  lamports: u64,
+) -> Pubkey {
+  try_wrap_sol(context, owner, lamports)
+    .await.unwrap()
+}
+
+/// Wraps `lamports` of `owner` into its native mint associated token account, creating
+/// the account if needed. Returns the address of the account.
+pub async fn try_wrap_sol(
+  context: &mut ProgramTestContext,
+  owner: &Keypair,
+  lamports: u64,
+) -> FrameworkResult<Pubkey> {
+  let payer = get_payer(context);
+  let token_account_address = get_associated_token_account_address(
+    &owner.pubkey(),
+    &NATIVE_MINT_ID,
+  );
+
+  let create_ata_ix = create_associated_token_account_idempotent_instruction(
+    &payer.pubkey(),
+    &owner.pubkey(),
+    &NATIVE_MINT_ID,
+  );
+  let transfer_ix = system_instruction::transfer(
+    &owner.pubkey(),
+    &token_account_address,
+    lamports,
+  );
+  let sync_native_ix = sync_native_instruction(
+    &TOKEN_PROGRAM_ID,
+    &token_account_address,
+  );
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[create_ata_ix, transfer_ix, sync_native_ix],
+    &[&payer, &owner],
+  ).await?;
+
+  Ok(token_account_address)
+}
+
+/// Resolves `recipient` to a token account: token accounts are used as is, any other
+/// address is treated as a wallet whose associated token account is created if needed.
+async fn check_and_create_ata_ix(
+  context: &mut ProgramTestContext,
+  recipient: &Pubkey,
+  payer: &Keypair,
+  token_mint: &Pubkey,
+  token_program_id: &Pubkey,
+) -> FrameworkResult<(Pubkey, Option<Instruction>)> {
+  let recipient_option = context.banks_client
+    .get_account(*recipient)
+    .await?;
+  if let Some(recipient_info) = recipient_option {
+    if check_token_program_id(&recipient_info.owner).is_ok() {
+      return Ok((*recipient, None));
+    }
+  }
+  let recipient_address = get_associated_token_account_address_with_program_id(
+    recipient,
+    token_mint,
+    token_program_id,
+  );
+  let create_ata_ix = create_associated_token_account_idempotent_with_program_id_instruction(
+    &payer.pubkey(),
+    &recipient,
+    &token_mint,
+    token_program_id,
+  );
+  Ok((recipient_address, Some(create_ata_ix)))
+}
+
+async fn try_set_token_account_authority<'a>(
+  context: &mut ProgramTestContext,
+  authority: impl Into<TokenAuthority<'a>>,
+  token_account_address: &Pubkey,
+  authority_type: AuthorityType,
+  new_authority_address: Option<&Pubkey>,
+) -> FrameworkResult<()> {
+  let token_program_id = try_get_token_program_id(context, token_account_address).await?;
+  let payer = get_payer(context);
+  let authority: TokenAuthority = authority.into();
+
+  let set_authority_ix = set_authority_instruction(
+    &token_program_id,
+    &authority.address(),
+    &token_account_address,
+    authority_type,
+    new_authority_address,
+  );
+  let set_authority_ix = authority.apply(set_authority_ix)?;
+
+  try_process_transaction(
+    context,
+    &payer,
+    &[set_authority_ix],
+    &authority.signers_with(&payer),
+  ).await
+}
+
+async fn get_token_return_data(
+  context: &mut ProgramTestContext,
+  instruction: Instruction,
+  token_program_id: &Pubkey,
+) -> FrameworkResult<Vec<u8>> {
+  let payer = get_payer(context);
+
+  let outcome = try_simulate_transaction(
+    context,
+    &payer,
+    &[instruction],
+    &[&payer],
+  ).await?;
+  outcome.result.clone()?;
+  // the runtime drops trailing zero bytes of the return data, all of it if it is all zeros
+  let return_data = outcome.return_data_of(token_program_id)
+    .unwrap_or_default();
+  Ok(return_data.to_vec())
+}
+
+/// Size of a token account for a mint, `None` if one of the mint extensions requires an
+/// account extension whose size is not known here.
+fn get_token_account_length(
+  token_program_id: &Pubkey,
+  token_mint_data: &[u8],
+) -> Option<usize> {
+  if *token_program_id == TOKEN_PROGRAM_ID {
+    return Some(TOKEN_ACCOUNT_LENGTH);
+  }
+  let mut extension_lengths = Vec::new();
+  for extension in unpack_token_extensions(token_mint_data).ok()? {
+    match extension {
+      // accounts of the mint carry a TransferFeeAmount extension
+      TokenExtension::TransferFeeConfig(_) => extension_lengths.push(8),
+      TokenExtension::MintCloseAuthority { .. } |
+      TokenExtension::DefaultAccountState { .. } |
+      TokenExtension::InterestBearingConfig(_) => {},
+      _ => return None,
+    }
+  }
+  if extension_lengths.is_empty() {
+    return Some(TOKEN_ACCOUNT_LENGTH);
+  }
+  let tlv_length: usize = extension_lengths.iter()
+    .map(|length| TLV_HEADER_LENGTH + length)
+    .sum();
+  Some(ACCOUNT_TYPE_OFFSET + 1 + tlv_length)
+}
+
+fn unpack_u64(
+  data: &[u8],
+) -> FrameworkResult<u64> {
+  if data.len() > 8 {
+    return Err(FrameworkError::Program(ProgramError::InvalidAccountData));
+  }
+  let mut bytes = [0u8; 8];
+  bytes[..data.len()].copy_from_slice(data);
+  Ok(u64::from_le_bytes(bytes))
+}
@@ -0,0 +1,137 @@
+pub mod program;
+
+use std::{
+  env,
+  fs,
+  path::{
+    PathBuf,
+  },
+  process,
+};
+use solana_sdk::{
+  signer::{
+    Signer,
+  },
+  system_program::{
+    ID as SYSTEM_PROGRAM_ID,
+  },
+};
+use solana_support_library::{
+  account::{
+    get_account,
+    get_token_account_named,
+    TokenName,
+  },
+  benchmark::{
+    BaselineMode,
+    ComputeUnitBaseline,
+  },
+  context::{
+    execute_transaction,
+    TestContextBuilder,
+  },
+  error::{
+    FrameworkError,
+  },
+  spl_token::{
+    ID as TOKEN_PROGRAM_ID,
+    transfer_token_instruction,
+  },
+  token::{
+    get_associated_token_account_address,
+  },
+};
+use test_framework::{
+  ID as PROGRAM_ID,
+};
+use crate::program::{
+  client,
+};
+
+fn get_baseline_path(
+) -> PathBuf {
+  PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    .join("tests")
+    .join("fixtures")
+    .join("baselines")
+    .join("compute_units.json")
+}
+
+#[tokio::test]
+async fn compute_unit_baseline_test() {
+  let mut baseline = ComputeUnitBaseline::new(&get_baseline_path())
+    .set_tolerance_percent(5);
+
+  let default_account = get_account(0);
+  let account_1 = get_account(1);
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+  let default_account_token_address = get_associated_token_account_address(&default_account.pubkey(), &token_mint_account.pubkey());
+  let account_1_token_address = get_associated_token_account_address(&account_1.pubkey(), &token_mint_account.pubkey());
+  let mut context = TestContextBuilder::new()
+    .add_program("test_framework", &PROGRAM_ID)
+    .add_account_with_lamports(&default_account.pubkey(), &SYSTEM_PROGRAM_ID, 1_000_000_000)
+    .add_token_mint(&token_mint_account.pubkey(), 6, Some(&default_account.pubkey()), None, 200_000_000)
+    .add_associated_token_account(&default_account.pubkey(), &token_mint_account.pubkey(), 100_000_000)
+    .add_associated_token_account(&account_1.pubkey(), &token_mint_account.pubkey(), 100_000_000)
+    .build()
+    .await;
+
+  let announce_ixn = client::create_announce_instruction(
+    &default_account.pubkey(),
+    vec![b'a'; 64],
+  );
+  let outcome = execute_transaction(&mut context, &default_account, &[announce_ixn], &[&default_account])
+    .await;
+  baseline.record_outcome("announce", &outcome);
+
+  let transfer_ixn = transfer_token_instruction(
+    &TOKEN_PROGRAM_ID,
+    &default_account.pubkey(),
+    &default_account_token_address,
+    &account_1_token_address,
+    1_000_000,
+  );
+  let forward_ixn = client::create_forward_instruction(&transfer_ixn);
+  let outcome = execute_transaction(&mut context, &default_account, &[forward_ixn], &[&default_account])
+    .await;
+  baseline.record_outcome("forward_token_transfer", &outcome);
+
+  let multiply_ixn = client::create_multiply_instruction(12, 34);
+  let outcome = execute_transaction(&mut context, &default_account, &[multiply_ixn], &[&default_account])
+    .await;
+  baseline.record_outcome("multiply", &outcome);
+
+  baseline.finish();
+}
+
+#[test]
+fn compute_unit_baseline_mode_test() {
+  let path = env::temp_dir()
+    .join(format!("compute_units_{}.json", process::id()));
+
+  let mut baseline = ComputeUnitBaseline::new(&path)
+    .set_mode(BaselineMode::Update);
+  baseline.record("announce", 1_000);
+  baseline.finish();
+
+  let mut baseline = ComputeUnitBaseline::new(&path)
+    .set_mode(BaselineMode::Check)
+    .set_tolerance_percent(10);
+  assert_eq!(baseline.get_baseline("announce"), Some(1_000));
+  assert!(baseline.try_record("announce", 1_100).is_ok());
+  let result = baseline.try_record("announce", 1_101);
+  assert!(matches!(result, Err(FrameworkError::ComputeUnitsExceeded { baseline: 1_000, compute_units: 1_101, .. })));
+  let result = baseline.try_record("multiply", 500);
+  assert!(matches!(result, Err(FrameworkError::MissingBaseline(scenario)) if scenario == "multiply"));
+
+  let mut baseline = baseline.set_mode(BaselineMode::Warn);
+  assert!(baseline.try_record("announce", 2_000).is_ok());
+  assert!(baseline.try_record("multiply", 500).is_ok());
+  assert_eq!(baseline.get_results().get("announce"), Some(&2_000));
+
+  fs::write(&path, "not a baseline").unwrap();
+  let result = ComputeUnitBaseline::try_new(&path);
+  assert!(matches!(result, Err(FrameworkError::InvalidBaseline(_))));
+
+  fs::remove_file(&path).unwrap();
+}
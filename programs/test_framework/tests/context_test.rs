@@ -0,0 +1,505 @@
+pub mod program;
+
+use anchor_lang::{
+  AccountSerialize,
+};
+use solana_program_test::{
+  processor,
+};
+use solana_sdk::{
+  clock::{
+    Clock,
+  },
+  epoch_schedule::{
+    EpochSchedule,
+  },
+  program_option::{
+    COption,
+  },
+  signer::{
+    Signer,
+  },
+  system_instruction,
+  system_program::{
+    ID as SYSTEM_PROGRAM_ID,
+  },
+  transaction::{
+    TransactionError,
+  },
+};
+use solana_support_library::{
+  account::{
+    get_account,
+    get_token_account_named,
+    TokenName,
+  },
+  context::{
+    assert_compute_units_within,
+    assert_instruction_compute_units_within,
+    assert_instruction_error,
+    BlockhashPolicy,
+    ClockController,
+    ComputeBudget,
+    execute_transaction,
+    execute_transaction_with_options,
+    get_anchor_account,
+    get_borsh_account,
+    get_packed_account,
+    set_anchor_account,
+    set_token_account,
+    set_token_mint,
+    simulate_transaction,
+    TestContextBuilder,
+    TransactionOptions,
+    try_get_anchor_account,
+    try_get_borsh_account,
+    try_get_packed_account,
+  },
+  error::{
+    FrameworkError,
+  },
+  spl_token::{
+    AccountState,
+    ID as TOKEN_PROGRAM_ID,
+    TokenAccount,
+    TokenMint,
+  },
+  spl_token_2022::{
+    ID as TOKEN_2022_PROGRAM_ID,
+  },
+  system::{
+    airdrop_lamport,
+    get_account_balance,
+    transfer_lamport,
+  },
+  token::{
+    get_associated_token_account_address,
+    get_associated_token_account_address_with_program_id,
+    get_token_account_balance,
+    get_token_account_info,
+    get_token_program_id,
+    get_token_supply,
+    transfer_token,
+    try_transfer_token,
+  },
+};
+use spl_token_2022::{
+  processor::{
+    Processor as Token2022Processor,
+  },
+};
+use test_framework::{
+  // `#[account]` resolves the owner of `Announcement` through `crate::ID`
+  ID,
+  ID as PROGRAM_ID,
+};
+use crate::program::{
+  client,
+  test_context::{
+    create_test_context,
+  },
+};
+use crate::state::{
+  Announcement,
+};
+
+mod state {
+  use anchor_lang::prelude::*;
+
+  /// Anchor account owned by the test program, only used to exercise the account readers.
+  #[account]
+  pub struct Announcement {
+    pub sender: Pubkey,
+    pub content: Vec<u8>,
+    pub slot: u64,
+  }
+}
+
+#[tokio::test]
+async fn execute_transaction_test() {
+  let mut context = create_test_context().await;
+
+  let default_account = get_account(0);
+  airdrop_lamport(&mut context, &default_account.pubkey(), 10_000_000).await;
+
+  let announce_ixn = client::create_announce_instruction(
+    &default_account.pubkey(),
+    b"Hello world!".to_vec(),
+  );
+
+  let outcome = execute_transaction(&mut context, &default_account, &[announce_ixn], &[&default_account])
+    .await;
+  assert!(outcome.is_success(), "announce failed");
+  assert!(outcome.compute_units_consumed > 0, "compute_units_consumed invalid");
+  assert!(outcome.log_messages.iter().any(|log| log == "Program log: Instruction: Announce"), "log_messages invalid");
+  assert!(outcome.return_data.is_none(), "return_data invalid");
+}
+
+#[tokio::test]
+async fn simulate_transaction_test() {
+  let mut context = create_test_context().await;
+
+  let default_account = get_account(0);
+  airdrop_lamport(&mut context, &default_account.pubkey(), 10_000_000).await;
+
+  let announce_ixn = client::create_announce_instruction(
+    &default_account.pubkey(),
+    b"An announcement should be short and must not exceed 64 characters.".to_vec(),
+  );
+
+  let outcome = simulate_transaction(&mut context, &default_account, &[announce_ixn], &[&default_account])
+    .await;
+  assert!(!outcome.is_success(), "announce must fail");
+  assert!(outcome.compute_units_consumed > 0, "compute_units_consumed invalid");
+  assert_instruction_error(
+    outcome.result,
+    0,
+    6000,
+  );
+}
+
+#[tokio::test]
+async fn test_context_builder_test() {
+  let default_account = get_account(0);
+  let account_2 = get_account(2);
+  let account_3 = get_account(3);
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+  let account_2_token_address = get_associated_token_account_address(&account_2.pubkey(), &token_mint_account.pubkey());
+
+  let mut context = TestContextBuilder::new()
+    .add_program("test_framework", &PROGRAM_ID)
+    .add_account_with_lamports(&default_account.pubkey(), &SYSTEM_PROGRAM_ID, 1_000_000_000)
+    .add_token_mint(&token_mint_account.pubkey(), 6, Some(&default_account.pubkey()), None, 500_000_000)
+    .add_associated_token_account(&account_2.pubkey(), &token_mint_account.pubkey(), 500_000_000)
+    .set_unix_timestamp(1_700_000_000)
+    .build()
+    .await;
+
+  let default_account_balance = get_account_balance(&mut context, &default_account.pubkey()).await;
+  assert!(default_account_balance == 1_000_000_000, "default_account_balance invalid");
+  let clock = context.banks_client.get_sysvar::<Clock>()
+    .await.unwrap();
+  assert!(clock.unix_timestamp == 1_700_000_000, "unix_timestamp invalid");
+
+  transfer_token(&mut context, &account_2, &account_2_token_address, &account_3.pubkey(), 100_000_000).await;
+
+  let account_2_token_balance = get_token_account_balance(&mut context, &account_2_token_address).await;
+  let account_3_token_address = get_associated_token_account_address(&account_3.pubkey(), &token_mint_account.pubkey());
+  let account_3_token_balance = get_token_account_balance(&mut context, &account_3_token_address).await;
+  assert!(account_2_token_balance == 400_000_000, "account_2_token_balance invalid");
+  assert!(account_3_token_balance == 100_000_000, "account_3_token_balance invalid");
+}
+
+#[tokio::test]
+async fn test_context_builder_token_2022_test() {
+  let account_2 = get_account(2);
+  let account_3 = get_account(3);
+  let token_mint_account = get_token_account_named(TokenName::USDC);
+  let account_2_token_address = get_associated_token_account_address_with_program_id(&account_2.pubkey(), &token_mint_account.pubkey(), &TOKEN_2022_PROGRAM_ID);
+
+  let mut context = TestContextBuilder::new()
+    .prefer_bpf(false)
+    .add_native_program("spl_token_2022", &TOKEN_2022_PROGRAM_ID, processor!(Token2022Processor::process))
+    .add_token_mint_with_program_id(&token_mint_account.pubkey(), 6, None, None, 500_000_000, &TOKEN_2022_PROGRAM_ID)
+    .add_associated_token_account_with_program_id(&account_2.pubkey(), &token_mint_account.pubkey(), 500_000_000, &TOKEN_2022_PROGRAM_ID)
+    .build()
+    .await;
+  assert_eq!(get_token_program_id(&mut context, &token_mint_account.pubkey()).await, TOKEN_2022_PROGRAM_ID);
+  assert_eq!(get_token_program_id(&mut context, &account_2_token_address).await, TOKEN_2022_PROGRAM_ID);
+
+  transfer_token(&mut context, &account_2, &account_2_token_address, &account_3.pubkey(), 100_000_000).await;
+
+  let account_2_token_balance = get_token_account_balance(&mut context, &account_2_token_address).await;
+  let account_3_token_address = get_associated_token_account_address_with_program_id(&account_3.pubkey(), &token_mint_account.pubkey(), &TOKEN_2022_PROGRAM_ID);
+  let account_3_token_balance = get_token_account_balance(&mut context, &account_3_token_address).await;
+  assert_eq!(account_2_token_balance, 400_000_000);
+  assert_eq!(account_3_token_balance, 100_000_000);
+}
+
+#[tokio::test]
+async fn get_account_test() {
+  let account_1 = get_account(1);
+  let account_2 = get_account(2);
+  let account_3 = get_account(3);
+  let account_4 = get_account(4);
+  let account_5 = get_account(5);
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+
+  let announcement = Announcement {
+    sender: account_1.pubkey(),
+    content: b"Hello world!".to_vec(),
+    slot: 42,
+  };
+  let mut announcement_data = Vec::new();
+  announcement.try_serialize(&mut announcement_data).unwrap();
+
+  let mut context = TestContextBuilder::new()
+    .add_account_with_data(&account_2.pubkey(), &PROGRAM_ID, &announcement_data, false)
+    .add_account_with_data(&account_3.pubkey(), &SYSTEM_PROGRAM_ID, &announcement_data, false)
+    .add_account_with_data(&account_4.pubkey(), &PROGRAM_ID, &announcement_data[8..], false)
+    .add_token_mint(&token_mint_account.pubkey(), 6, Some(&account_1.pubkey()), None, 500_000_000)
+    .build()
+    .await;
+
+  let stored_announcement = get_anchor_account::<Announcement>(&mut context, &account_2.pubkey()).await
+    .unwrap();
+  assert_eq!(stored_announcement.sender, account_1.pubkey());
+  assert_eq!(stored_announcement.content, b"Hello world!".to_vec());
+  assert_eq!(stored_announcement.slot, 42);
+  assert!(get_anchor_account::<Announcement>(&mut context, &account_5.pubkey()).await.is_none());
+
+  let result = try_get_anchor_account::<Announcement>(&mut context, &account_3.pubkey()).await;
+  assert!(matches!(result, Err(FrameworkError::InvalidAccountOwner { actual, .. }) if actual == SYSTEM_PROGRAM_ID));
+  let result = try_get_anchor_account::<Announcement>(&mut context, &account_4.pubkey()).await;
+  assert!(matches!(result, Err(FrameworkError::InvalidAccountData { .. })));
+
+  let stored_announcement = get_borsh_account::<Announcement>(&mut context, &account_4.pubkey(), &PROGRAM_ID).await
+    .unwrap();
+  assert_eq!(stored_announcement.slot, 42);
+  let result = try_get_borsh_account::<Announcement>(&mut context, &account_4.pubkey(), &SYSTEM_PROGRAM_ID).await;
+  assert!(matches!(result, Err(FrameworkError::InvalidAccountOwner { actual, .. }) if actual == PROGRAM_ID));
+
+  let token_mint = get_packed_account::<TokenMint>(&mut context, &token_mint_account.pubkey(), &TOKEN_PROGRAM_ID).await
+    .unwrap();
+  assert_eq!(token_mint.supply, 500_000_000);
+  let result = try_get_packed_account::<TokenMint>(&mut context, &token_mint_account.pubkey(), &PROGRAM_ID).await;
+  assert!(matches!(result, Err(FrameworkError::InvalidAccountOwner { actual, .. }) if actual == TOKEN_PROGRAM_ID));
+}
+
+#[tokio::test]
+async fn set_account_test() {
+  let mut context = create_test_context().await;
+  let account_1 = get_account(1);
+  let account_2 = get_account(2);
+  let account_3 = get_account(3);
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+
+  set_token_mint(&mut context, &token_mint_account.pubkey(), &TokenMint {
+    mint_authority: COption::Some(account_1.pubkey()),
+    supply: u64::MAX,
+    decimals: 6,
+    is_initialized: true,
+    freeze_authority: COption::None,
+  }).await;
+  assert_eq!(get_token_supply(&mut context, &token_mint_account.pubkey()).await, u64::MAX);
+
+  let account_2_token_address = get_associated_token_account_address(&account_2.pubkey(), &token_mint_account.pubkey());
+  set_token_account(&mut context, &account_2_token_address, &TokenAccount {
+    mint: token_mint_account.pubkey(),
+    owner: account_2.pubkey(),
+    amount: 100_000_000,
+    state: AccountState::Frozen,
+    ..TokenAccount::default()
+  }).await;
+  let token_account = get_token_account_info(&mut context, &account_2_token_address).await;
+  assert!(token_account.is_frozen());
+
+  let txn_result = try_transfer_token(&mut context, &account_2, &account_2_token_address, &account_3.pubkey(), 1_000_000).await;
+  assert_instruction_error(
+    txn_result,
+    1,
+    17, // TokenError::AccountFrozen
+  );
+
+  let announcement = Announcement {
+    sender: account_1.pubkey(),
+    content: b"Hello world!".to_vec(),
+    slot: 42,
+  };
+  set_anchor_account(&mut context, &account_3.pubkey(), &announcement).await;
+  let stored_announcement = get_anchor_account::<Announcement>(&mut context, &account_3.pubkey()).await
+    .unwrap();
+  assert_eq!(stored_announcement.content, announcement.content);
+}
+
+#[tokio::test]
+async fn clock_controller_test() {
+  let mut context = create_test_context().await;
+  let clock_controller = ClockController::new(500);
+  let epoch_schedule = context.banks_client.get_sysvar::<EpochSchedule>()
+    .await.unwrap();
+  let start_clock = context.banks_client.get_sysvar::<Clock>()
+    .await.unwrap();
+
+  let clock = clock_controller.forward_time(&mut context, 10).await;
+  assert_eq!(clock.slot, start_clock.slot + 20);
+  assert_eq!(clock.unix_timestamp, start_clock.unix_timestamp + 10);
+
+  let clock = clock_controller.forward_slots(&mut context, 4).await;
+  assert_eq!(clock.slot, start_clock.slot + 24);
+  assert_eq!(clock.unix_timestamp, start_clock.unix_timestamp + 12);
+
+  let target_epoch = clock.epoch + 2;
+  let clock = clock_controller.warp_to_epoch(&mut context, target_epoch).await;
+  assert_eq!(clock.epoch, target_epoch);
+  assert_eq!(clock.slot, epoch_schedule.get_first_slot_in_epoch(target_epoch));
+  assert_eq!(clock.epoch_start_timestamp, clock.unix_timestamp);
+  assert_eq!(clock.leader_schedule_epoch, epoch_schedule.get_leader_schedule_epoch(clock.slot));
+  let stored_clock = context.banks_client.get_sysvar::<Clock>()
+    .await.unwrap();
+  assert_eq!(stored_clock, clock);
+
+  let clock = clock_controller.set_clock(&mut context, clock.slot + 10, 1_700_000_000).await;
+  assert_eq!(clock.unix_timestamp, 1_700_000_000);
+  assert_eq!(clock.epoch, target_epoch);
+  assert!(clock_controller.try_set_clock(&mut context, clock.slot - 1, 1_700_000_000).await.is_err());
+
+  let clock = clock_controller.forward_time(&mut context, 0).await;
+  assert_eq!(clock, context.banks_client.get_sysvar::<Clock>().await.unwrap());
+
+  // 5 slots of 400 ms add up to 2 seconds even when forwarded one by one
+  let clock_controller = ClockController::default();
+  let start_clock = clock;
+  let mut clock = start_clock.clone();
+  for _ in 0..5 {
+    clock = clock_controller.forward_slots(&mut context, 1).await;
+  }
+  assert_eq!(clock.slot, start_clock.slot + 5);
+  assert_eq!(clock.unix_timestamp, start_clock.unix_timestamp + 2);
+}
+
+#[tokio::test]
+async fn blockhash_policy_test() {
+  let mut context = create_test_context().await;
+  let default_account = get_account(0);
+  let account_1 = get_account(1);
+  airdrop_lamport(&mut context, &default_account.pubkey(), 1_000_000_000).await;
+
+  let transfer_ixn = system_instruction::transfer(
+    &default_account.pubkey(),
+    &account_1.pubkey(),
+    10_000_000,
+  );
+
+  // the second identical transfer is sent again with a new blockhash by default
+  transfer_lamport(&mut context, &default_account, &account_1.pubkey(), 10_000_000).await;
+  transfer_lamport(&mut context, &default_account, &account_1.pubkey(), 10_000_000).await;
+  let account_1_balance = get_account_balance(&mut context, &account_1.pubkey()).await;
+  assert_eq!(account_1_balance, 20_000_000);
+
+  let options = TransactionOptions::new()
+    .set_blockhash_policy(BlockhashPolicy::Reuse);
+  let outcome = execute_transaction_with_options(
+    &mut context,
+    &default_account,
+    &[transfer_ixn.clone()],
+    &[&default_account],
+    &options,
+  ).await;
+  assert_eq!(outcome.result, Ok(()));
+
+  // the same payer, signers and blockhash make a duplicate
+  let options = TransactionOptions::new()
+    .set_blockhash_policy(BlockhashPolicy::Reuse);
+  let outcome = execute_transaction_with_options(
+    &mut context,
+    &default_account,
+    &[transfer_ixn.clone()],
+    &[&default_account],
+    &options,
+  ).await;
+  assert_eq!(outcome.result, Err(TransactionError::AlreadyProcessed));
+  let options = TransactionOptions::new()
+    .set_blockhash_policy(BlockhashPolicy::FailOnDuplicate);
+  let outcome = execute_transaction_with_options(
+    &mut context,
+    &default_account,
+    &[transfer_ixn.clone()],
+    &[&default_account],
+    &options,
+  ).await;
+  assert_eq!(outcome.result, Err(TransactionError::AlreadyProcessed));
+  let account_1_balance = get_account_balance(&mut context, &account_1.pubkey()).await;
+  assert_eq!(account_1_balance, 30_000_000);
+
+  // the default policy sends the duplicate once more with a new blockhash
+  let last_blockhash = context.last_blockhash;
+  let outcome = execute_transaction(
+    &mut context,
+    &default_account,
+    &[transfer_ixn.clone()],
+    &[&default_account],
+  ).await;
+  assert_eq!(outcome.result, Ok(()));
+  assert_ne!(context.last_blockhash, last_blockhash);
+
+  let last_blockhash = context.last_blockhash;
+  let options = TransactionOptions::new()
+    .set_blockhash_policy(BlockhashPolicy::AlwaysRefresh);
+  let outcome = execute_transaction_with_options(
+    &mut context,
+    &default_account,
+    &[transfer_ixn],
+    &[&default_account],
+    &options,
+  ).await;
+  assert_eq!(outcome.result, Ok(()));
+  assert_ne!(context.last_blockhash, last_blockhash);
+  let account_1_balance = get_account_balance(&mut context, &account_1.pubkey()).await;
+  assert_eq!(account_1_balance, 50_000_000);
+}
+
+#[tokio::test]
+async fn compute_budget_test() {
+  let mut context = create_test_context().await;
+  let default_account = get_account(0);
+  airdrop_lamport(&mut context, &default_account.pubkey(), 10_000_000).await;
+
+  let announce_ixn = client::create_announce_instruction(
+    &default_account.pubkey(),
+    b"Hello world!".to_vec(),
+  );
+  let balance_before = get_account_balance(&mut context, &default_account.pubkey()).await;
+  let outcome = execute_transaction(
+    &mut context,
+    &default_account,
+    &[announce_ixn],
+    &[&default_account],
+  ).await;
+  assert!(outcome.is_success(), "announce failed");
+  let balance_after = get_account_balance(&mut context, &default_account.pubkey()).await;
+  let base_fee = balance_before - balance_after;
+
+  let compute_budget = ComputeBudget::new()
+    .set_unit_limit(50_000)
+    .set_unit_price(2_000_000);
+  let options = TransactionOptions::new()
+    .set_compute_budget(compute_budget);
+  let announce_ixn = client::create_announce_instruction(
+    &default_account.pubkey(),
+    b"Hello again!".to_vec(),
+  );
+  let outcome = execute_transaction_with_options(
+    &mut context,
+    &default_account,
+    &[announce_ixn],
+    &[&default_account],
+    &options,
+  ).await;
+  assert!(outcome.is_success(), "announce failed");
+  assert_compute_units_within(&outcome, 50_000);
+  assert_instruction_compute_units_within(&outcome, 0, 50_000);
+  let balance_before = balance_after;
+  let balance_after = get_account_balance(&mut context, &default_account.pubkey()).await;
+  assert_eq!(compute_budget.prioritization_fee(1), 100_000);
+  assert_eq!(balance_before - balance_after, base_fee + 100_000);
+
+  let options = TransactionOptions::new()
+    .set_compute_budget(ComputeBudget::new().set_unit_limit(1_000));
+  let announce_ixn = client::create_announce_instruction(
+    &default_account.pubkey(),
+    b"Out of budget".to_vec(),
+  );
+  let outcome = execute_transaction_with_options(
+    &mut context,
+    &default_account,
+    &[announce_ixn],
+    &[&default_account],
+    &options,
+  ).await;
+  // the error is reported at the announce instruction, not at the prepended budget request
+  assert!(
+    matches!(outcome.result, Err(TransactionError::InstructionError(0, _))),
+    "announce must exceed the compute budget",
+  );
+}
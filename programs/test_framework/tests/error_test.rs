@@ -1,23 +1,38 @@
-pub mod framework;
 pub mod program;
 
+use anchor_lang::{
+  error::{
+    ErrorCode as AnchorErrorCode,
+  },
+};
 use solana_sdk::{
   signer::{
     Signer,
   },
 };
-use crate::framework::{
+use solana_support_library::{
   account::{
     get_account,
   },
   context::{
+    assert_anchor_error,
     assert_instruction_error,
+    assert_transaction_anchor_error,
+    execute_transaction,
     process_transaction2,
   },
+  log_message::{
+    find_anchor_error,
+  },
   system::{
     airdrop_lamport,
   },
 };
+use test_framework::{
+  error::{
+    ErrorCode,
+  },
+};
 use crate::program::{
   client,
   test_context::{
@@ -45,3 +60,67 @@ async fn announce_test() {
     6000,
   );
 }
+
+#[tokio::test]
+async fn announce_content_too_long_test() {
+  let mut context = create_test_context().await;
+
+  let default_account = get_account(0);
+  airdrop_lamport(&mut context, &default_account.pubkey(), 10_000_000).await;
+
+  let announce_ixn = client::create_announce_instruction(
+    &default_account.pubkey(),
+    b"An announcement should be short and must not exceed 64 characters.".to_vec(),
+  );
+
+  let outcome = execute_transaction(&mut context, &default_account, &[announce_ixn], &[&default_account])
+    .await;
+  assert_transaction_anchor_error(
+    &outcome,
+    0,
+    ErrorCode::ContentTooLong,
+  );
+  let anchor_error = find_anchor_error(&outcome.log_messages)
+    .unwrap();
+  assert_eq!(anchor_error.error_code, "ContentTooLong");
+  assert_eq!(anchor_error.error_number, 6000);
+  assert_eq!(anchor_error.error_message, "Content is too long");
+
+  let announce_ixn = client::create_announce_instruction(
+    &default_account.pubkey(),
+    [0u8; 65].to_vec(),
+  );
+  let txn_result = process_transaction2(&mut context, &default_account, &[announce_ixn], &[&default_account])
+    .await;
+  assert_anchor_error(
+    txn_result,
+    0,
+    ErrorCode::ContentTooLong,
+  );
+}
+
+#[tokio::test]
+async fn announce_sender_not_signer_test() {
+  let mut context = create_test_context().await;
+
+  let default_account = get_account(0);
+  airdrop_lamport(&mut context, &default_account.pubkey(), 10_000_000).await;
+
+  let account_1 = get_account(1);
+  let mut announce_ixn = client::create_announce_instruction(
+    &account_1.pubkey(),
+    b"Hello world!".to_vec(),
+  );
+  announce_ixn.accounts[0].is_signer = false;
+
+  let outcome = execute_transaction(&mut context, &default_account, &[announce_ixn], &[&default_account])
+    .await;
+  assert_transaction_anchor_error(
+    &outcome,
+    0,
+    AnchorErrorCode::AccountNotSigner,
+  );
+  let anchor_error = find_anchor_error(&outcome.log_messages)
+    .unwrap();
+  assert_eq!(anchor_error.error_origin, Some("sender".to_string()));
+}
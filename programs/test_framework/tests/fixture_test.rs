@@ -0,0 +1,112 @@
+use std::{
+  path::{
+    PathBuf,
+  },
+};
+use solana_sdk::{
+  program_pack::{
+    Pack,
+  },
+  signer::{
+    Signer,
+  },
+};
+use solana_support_library::{
+  account::{
+    get_account,
+    get_token_account_named,
+    TokenName,
+  },
+  context::{
+    TestContextBuilder,
+  },
+  error::{
+    FrameworkError,
+  },
+  fixture::{
+    export_account_fixtures,
+    read_account_fixture,
+  },
+  spl_token::{
+    TokenMint,
+  },
+  system::{
+    airdrop_lamport,
+  },
+  token::{
+    mint_token,
+  },
+};
+
+fn get_fixtures_directory(
+) -> PathBuf {
+  PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    .join("tests")
+    .join("fixtures")
+}
+
+#[tokio::test]
+async fn load_account_fixtures_test() {
+  let mut context = TestContextBuilder::new()
+    .add_account_fixtures(&get_fixtures_directory())
+    .build()
+    .await;
+
+  let default_account = get_account(0);
+  let token_mint_account = get_token_account_named(TokenName::USDC);
+  let token_mint_info = context.banks_client.get_account(token_mint_account.pubkey())
+    .await.unwrap().unwrap();
+  let token_mint = TokenMint::unpack(&token_mint_info.data).unwrap();
+  assert!(token_mint.decimals == 6, "decimals invalid");
+  assert!(token_mint.supply == 1_000_000_000_000, "supply invalid");
+  assert!(token_mint.mint_authority.unwrap() == default_account.pubkey(), "mint_authority invalid");
+
+  let account_2 = get_account(2);
+  mint_token(&mut context, &default_account, &token_mint_account.pubkey(), &account_2.pubkey(), 100_000_000).await;
+}
+
+#[test]
+fn load_invalid_account_fixture_test() {
+  let result = TestContextBuilder::new()
+    .try_add_account_fixture(&get_fixtures_directory().join("missing.json"));
+  assert!(matches!(result, Err(FrameworkError::Io(_))), "missing fixture must fail");
+
+  let fixture_path = std::env::temp_dir()
+    .join("solana_support_library_invalid_fixture.json");
+  std::fs::write(&fixture_path, "{}").unwrap();
+  let result = TestContextBuilder::new()
+    .try_add_account_fixture(&fixture_path);
+  assert!(matches!(result, Err(FrameworkError::InvalidFixture(_))), "malformed fixture must fail");
+  std::fs::remove_file(&fixture_path).unwrap();
+}
+
+#[tokio::test]
+async fn export_account_fixtures_test() {
+  let mut context = TestContextBuilder::new()
+    .build()
+    .await;
+
+  let default_account = get_account(0);
+  airdrop_lamport(&mut context, &default_account.pubkey(), 1_000_000_000).await;
+
+  let output_directory = std::env::temp_dir()
+    .join("solana_support_library_fixtures");
+  export_account_fixtures(&mut context, &[default_account.pubkey()], &output_directory)
+    .await.unwrap();
+
+  let fixture_path = output_directory.join(format!("{}.json", default_account.pubkey()));
+  let content = std::fs::read_to_string(&fixture_path).unwrap();
+  assert!(content.ends_with("}\n"), "fixture must end with a newline");
+  let (address, account) = read_account_fixture(&fixture_path)
+    .unwrap();
+  assert!(address == default_account.pubkey(), "address invalid");
+  assert!(account.lamports == 1_000_000_000, "lamports invalid");
+
+  let mut context = TestContextBuilder::new()
+    .add_account(&address, account)
+    .build()
+    .await;
+  let account_info = context.banks_client.get_account(default_account.pubkey())
+    .await.unwrap().unwrap();
+  assert!(account_info.lamports == 1_000_000_000, "lamports invalid");
+}
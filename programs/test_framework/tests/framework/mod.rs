@@ -1,7 +0,0 @@
-pub mod account;
-pub mod context;
-pub mod merkle_tree;
-pub mod system;
-pub mod token;
-mod spl_atoken;
-mod spl_token;
@@ -0,0 +1,130 @@
+pub mod program;
+
+use solana_sdk::{
+  clock::{
+    Clock,
+  },
+  signer::{
+    Signer,
+  },
+  system_instruction,
+  system_program::{
+    ID as SYSTEM_PROGRAM_ID,
+  },
+};
+use solana_support_library::{
+  account::{
+    get_account,
+  },
+  context::{
+    execute_transaction,
+  },
+  log_message::{
+    decode_events,
+    find_failed_invocation,
+    parse_log_messages,
+  },
+  system::{
+    airdrop_lamport,
+  },
+};
+use test_framework::{
+  event::{
+    AnnouncedEvent,
+  },
+  ID as PROGRAM_ID,
+};
+use crate::program::{
+  client,
+  test_context::{
+    create_test_context,
+  },
+};
+
+#[tokio::test]
+async fn decode_announced_event_test() {
+  let mut context = create_test_context().await;
+
+  let default_account = get_account(0);
+  airdrop_lamport(&mut context, &default_account.pubkey(), 10_000_000).await;
+
+  let clock = context.banks_client.get_sysvar::<Clock>()
+    .await.unwrap();
+  let announce_ixn = client::create_announce_instruction(
+    &default_account.pubkey(),
+    b"Hello world!".to_vec(),
+  );
+  let outcome = execute_transaction(&mut context, &default_account, &[announce_ixn], &[&default_account])
+    .await;
+  assert!(outcome.is_success(), "announce failed");
+
+  let invocations = decode_events::<AnnouncedEvent>(&outcome.log_messages);
+  assert_eq!(invocations.len(), 1);
+  assert_eq!(invocations[0].program_id, PROGRAM_ID);
+  assert_eq!(invocations[0].depth, 1);
+  assert_eq!(invocations[0].events.len(), 1);
+
+  let event = &invocations[0].events[0];
+  assert_eq!(event.address, default_account.pubkey());
+  assert_eq!(event.content, b"Hello world!".to_vec());
+  assert_eq!(event.slot, clock.slot);
+  assert_eq!(event.timestamp, clock.unix_timestamp);
+}
+
+#[tokio::test]
+async fn parse_forward_invocation_test() {
+  let mut context = create_test_context().await;
+
+  let default_account = get_account(0);
+  airdrop_lamport(&mut context, &default_account.pubkey(), 10_000_000).await;
+
+  let account_1 = get_account(1);
+  let transfer_ixn = system_instruction::transfer(
+    &default_account.pubkey(),
+    &account_1.pubkey(),
+    1_000_000,
+  );
+  let forward_ixn = client::create_forward_instruction(&transfer_ixn);
+  let outcome = execute_transaction(&mut context, &default_account, &[forward_ixn], &[&default_account])
+    .await;
+  assert!(outcome.is_success(), "forward failed");
+
+  let invocations = parse_log_messages(&outcome.log_messages);
+  assert_eq!(invocations.len(), 1);
+  assert_eq!(invocations[0].program_id, PROGRAM_ID);
+  assert_eq!(invocations[0].depth, 1);
+  assert!(invocations[0].is_success);
+  assert!(invocations[0].messages.contains(&"Instruction: Forward".to_string()));
+  assert!(invocations[0].compute_units_consumed.unwrap() > 0);
+  assert_eq!(invocations[0].children.len(), 1);
+  assert_eq!(invocations[0].children[0].program_id, SYSTEM_PROGRAM_ID);
+  assert_eq!(invocations[0].children[0].depth, 2);
+  assert!(invocations[0].children[0].is_success);
+}
+
+#[tokio::test]
+async fn parse_failed_forward_invocation_test() {
+  let mut context = create_test_context().await;
+
+  let default_account = get_account(0);
+  airdrop_lamport(&mut context, &default_account.pubkey(), 10_000_000).await;
+
+  let account_1 = get_account(1);
+  let transfer_ixn = system_instruction::transfer(
+    &default_account.pubkey(),
+    &account_1.pubkey(),
+    1_000_000_000,
+  );
+  let forward_ixn = client::create_forward_instruction(&transfer_ixn);
+  let outcome = execute_transaction(&mut context, &default_account, &[forward_ixn], &[&default_account])
+    .await;
+  assert!(!outcome.is_success(), "forward must fail");
+
+  let invocations = parse_log_messages(&outcome.log_messages);
+  assert!(!invocations[0].is_success);
+  let failed_invocation = find_failed_invocation(&invocations)
+    .unwrap();
+  assert_eq!(failed_invocation.program_id, SYSTEM_PROGRAM_ID);
+  assert_eq!(failed_invocation.depth, 2);
+  assert_eq!(failed_invocation.failure_reason, Some("custom program error: 0x1".to_string()));
+}
@@ -1,4 +1,3 @@
-pub mod framework;
 pub mod program;
 
 use anchor_lang::{
@@ -9,12 +8,10 @@ use solana_sdk::{
     Signer,
   },
 };
-use crate::{
-  framework::{
-    account as test_account,
-    merkle_tree::{
-      MerkleTree,
-    },
+use solana_support_library::{
+  account as test_account,
+  merkle_tree::{
+    MerkleTree,
   },
 };
 
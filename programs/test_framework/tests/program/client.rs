@@ -4,6 +4,7 @@ use anchor_lang::{
 };
 use solana_sdk::{
   instruction::{
+    AccountMeta,
     Instruction,
   },
   pubkey::{
@@ -35,3 +36,41 @@ pub fn create_announce_instruction(
     program_id: PROGRAM_ID,
   }
 }
+
+pub fn create_forward_instruction(
+  instruction: &Instruction,
+) -> Instruction {
+
+  let data = p_instruction::Forward {
+    data: instruction.data.clone(),
+  }.data();
+
+  let mut accounts = p_context::ForwardContext {}.to_account_metas(None);
+  accounts.extend(instruction.accounts.iter().cloned());
+  accounts.push(AccountMeta::new_readonly(instruction.program_id, false));
+
+  Instruction {
+    data,
+    accounts,
+    program_id: PROGRAM_ID,
+  }
+}
+
+pub fn create_multiply_instruction(
+  first_number: u16,
+  second_number: u16,
+) -> Instruction {
+
+  let data = p_instruction::Multiply {
+    first_number,
+    second_number,
+  }.data();
+
+  let accounts = p_context::MultiplyContext {}.to_account_metas(None);
+
+  Instruction {
+    data,
+    accounts,
+    program_id: PROGRAM_ID,
+  }
+}
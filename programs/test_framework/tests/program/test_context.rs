@@ -1,7 +1,7 @@
 use solana_program_test::{
   ProgramTestContext,
 };
-use crate::framework::{
+use solana_support_library::{
   context::{
     create_context,
   }
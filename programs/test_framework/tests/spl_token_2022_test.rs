@@ -0,0 +1,101 @@
+use solana_sdk::{
+  program_option::{
+    COption,
+  },
+  program_pack::{
+    Pack,
+  },
+  signer::{
+    Signer,
+  },
+};
+use solana_support_library::{
+  account::{
+    get_account,
+    get_token_account_named,
+    TokenName,
+  },
+  spl_atoken::{
+    get_associated_token_account_address,
+    get_associated_token_account_address_with_program_id,
+  },
+  spl_token::{
+    AccountState,
+    TokenMint,
+    TOKEN_ACCOUNT_LENGTH,
+  },
+  spl_token_2022::{
+    ID as TOKEN_2022_PROGRAM_ID,
+    TokenAccountType,
+    TokenExtension,
+    TransferFee,
+    unpack_token_extensions,
+    unpack_token_mint,
+  },
+};
+
+#[test]
+fn unpack_token_extensions_test() {
+  let authority = get_account(1);
+  let token_mint = TokenMint {
+    mint_authority: COption::Some(authority.pubkey()),
+    supply: 1_000_000,
+    decimals: 6,
+    is_initialized: true,
+    freeze_authority: COption::None,
+  };
+  let mut data = vec![0u8; TOKEN_ACCOUNT_LENGTH];
+  TokenMint::pack(token_mint, &mut data[..TokenMint::LEN]).unwrap();
+  data.push(TokenAccountType::Mint as u8);
+
+  // MintCloseAuthority
+  data.extend_from_slice(&3u16.to_le_bytes());
+  data.extend_from_slice(&32u16.to_le_bytes());
+  data.extend_from_slice(authority.pubkey().as_ref());
+  // DefaultAccountState
+  data.extend_from_slice(&6u16.to_le_bytes());
+  data.extend_from_slice(&1u16.to_le_bytes());
+  data.push(AccountState::Frozen as u8);
+  // TransferFeeConfig without authorities
+  data.extend_from_slice(&1u16.to_le_bytes());
+  data.extend_from_slice(&108u16.to_le_bytes());
+  data.extend_from_slice(&[0u8; 64]);
+  data.extend_from_slice(&0u64.to_le_bytes());
+  for (epoch, maximum_fee, basis_points) in [(0u64, 0u64, 0u16), (5u64, 5_000u64, 50u16)] {
+    data.extend_from_slice(&epoch.to_le_bytes());
+    data.extend_from_slice(&maximum_fee.to_le_bytes());
+    data.extend_from_slice(&basis_points.to_le_bytes());
+  }
+  // NonTransferable
+  data.extend_from_slice(&9u16.to_le_bytes());
+  data.extend_from_slice(&0u16.to_le_bytes());
+
+  assert_eq!(unpack_token_mint(&data).unwrap(), token_mint);
+
+  let extensions = unpack_token_extensions(&data).unwrap();
+  assert_eq!(extensions.len(), 4);
+  assert_eq!(extensions[0], TokenExtension::MintCloseAuthority { close_authority: Some(authority.pubkey()) });
+  assert_eq!(extensions[1], TokenExtension::DefaultAccountState { state: AccountState::Frozen });
+  let transfer_fee_config = match &extensions[2] {
+    TokenExtension::TransferFeeConfig(transfer_fee_config) => transfer_fee_config,
+    extension => panic!("unexpected extension {:?}", extension),
+  };
+  assert_eq!(transfer_fee_config.transfer_fee_config_authority, None);
+  assert_eq!(transfer_fee_config.get_epoch_fee(4), &TransferFee::default());
+  assert_eq!(transfer_fee_config.get_epoch_fee(5).calculate_fee(1_000_000), 5_000);
+  assert_eq!(transfer_fee_config.get_epoch_fee(5).calculate_fee(10_000), 50);
+  assert_eq!(extensions[3], TokenExtension::NonTransferable);
+
+  assert!(unpack_token_extensions(&data[..TokenMint::LEN]).unwrap().is_empty());
+  data[TOKEN_ACCOUNT_LENGTH] = TokenAccountType::Account as u8;
+  assert!(unpack_token_mint(&data).is_err());
+}
+
+#[test]
+fn associated_token_account_address_test() {
+  let account_1 = get_account(1);
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+  let token_address = get_associated_token_account_address(&account_1.pubkey(), &token_mint_account.pubkey());
+  let token_2022_address = get_associated_token_account_address_with_program_id(&account_1.pubkey(), &token_mint_account.pubkey(), &TOKEN_2022_PROGRAM_ID);
+  assert_ne!(token_address, token_2022_address);
+}
@@ -0,0 +1,124 @@
+use solana_sdk::{
+  program_option::{
+    COption,
+  },
+  signer::{
+    Signer,
+  },
+};
+use solana_support_library::{
+  account::{
+    get_account,
+    get_token_account_named,
+    TokenName,
+  },
+  spl_token::{
+    amount_to_ui_amount_string,
+    amount_to_ui_amount_string_trimmed,
+    AuthorityType,
+    decode_token_instruction,
+    ID as TOKEN_PROGRAM_ID,
+    initialize_token_multisig_instruction,
+    TokenAccountRole,
+    TokenInstruction,
+    transfer_token_checked_instruction,
+    try_ui_amount_into_amount,
+  },
+};
+
+#[test]
+fn token_instruction_unpack_test() {
+  let account_1 = get_account(1);
+  let instructions = vec![
+    TokenInstruction::InitializeMint { decimals: 6, mint_authority: account_1.pubkey(), freeze_authority: COption::None },
+    TokenInstruction::InitializeAccount,
+    TokenInstruction::InitializeMultisig { m: 2 },
+    TokenInstruction::Transfer { amount: 1 },
+    TokenInstruction::Approve { amount: 2 },
+    TokenInstruction::Revoke,
+    TokenInstruction::SetAuthority { authority_type: AuthorityType::CloseAccount, new_authority: COption::Some(account_1.pubkey()) },
+    TokenInstruction::MintTo { amount: 3 },
+    TokenInstruction::Burn { amount: 4 },
+    TokenInstruction::CloseAccount,
+    TokenInstruction::FreezeAccount,
+    TokenInstruction::ThawAccount,
+    TokenInstruction::TransferChecked { amount: 5, decimals: 6 },
+    TokenInstruction::ApproveChecked { amount: 6, decimals: 6 },
+    TokenInstruction::MintToChecked { amount: 7, decimals: 6 },
+    TokenInstruction::BurnChecked { amount: 8, decimals: 6 },
+    TokenInstruction::InitializeAccount2 { owner: account_1.pubkey() },
+    TokenInstruction::SyncNative,
+    TokenInstruction::InitializeAccount3 { owner: account_1.pubkey() },
+    TokenInstruction::InitializeMultisig2 { m: 3 },
+    TokenInstruction::InitializeMint2 { decimals: 9, mint_authority: account_1.pubkey(), freeze_authority: COption::Some(account_1.pubkey()) },
+    TokenInstruction::GetAccountDataSize,
+    TokenInstruction::InitializeImmutableOwner,
+    TokenInstruction::AmountToUiAmount { amount: 9 },
+    TokenInstruction::UiAmountToAmount { ui_amount: "1.25".to_string() },
+  ];
+  for instruction in instructions {
+    let data = instruction.pack();
+    assert_eq!(TokenInstruction::unpack(&data).unwrap(), instruction);
+  }
+  assert!(TokenInstruction::unpack(&[]).is_err());
+  assert!(TokenInstruction::unpack(&[25]).is_err());
+  assert!(TokenInstruction::unpack(&[3, 1, 0]).is_err());
+}
+
+#[test]
+fn decode_token_instruction_test() {
+  let account_1 = get_account(1);
+  let account_2 = get_account(2);
+  let account_3 = get_account(3);
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+
+  let instruction = transfer_token_checked_instruction(
+    &TOKEN_PROGRAM_ID,
+    &account_1.pubkey(),
+    &account_2.pubkey(),
+    &token_mint_account.pubkey(),
+    &account_3.pubkey(),
+    100_000_000,
+    6,
+  );
+  let decoded = decode_token_instruction(&instruction).unwrap();
+  assert_eq!(decoded.instruction, TokenInstruction::TransferChecked { amount: 100_000_000, decimals: 6 });
+  assert_eq!(decoded.account(TokenAccountRole::Source).unwrap().pubkey, account_2.pubkey());
+  assert_eq!(decoded.account(TokenAccountRole::Mint).unwrap().pubkey, token_mint_account.pubkey());
+  assert_eq!(decoded.account(TokenAccountRole::Destination).unwrap().pubkey, account_3.pubkey());
+  let authority = decoded.account(TokenAccountRole::Authority).unwrap();
+  assert_eq!(authority.pubkey, account_1.pubkey());
+  assert!(authority.is_signer);
+
+  let multisig_account = get_account(4);
+  let instruction = initialize_token_multisig_instruction(
+    &TOKEN_PROGRAM_ID,
+    &multisig_account.pubkey(),
+    &[&account_1.pubkey(), &account_2.pubkey(), &account_3.pubkey()],
+    2,
+  );
+  let decoded = decode_token_instruction(&instruction).unwrap();
+  assert_eq!(decoded.account(TokenAccountRole::Multisig).unwrap().pubkey, multisig_account.pubkey());
+  assert_eq!(decoded.accounts(TokenAccountRole::Signer).len(), 3);
+}
+
+#[test]
+fn ui_amount_test() {
+  assert_eq!(amount_to_ui_amount_string(1_500_000, 6), "1.500000");
+  assert_eq!(amount_to_ui_amount_string(15, 6), "0.000015");
+  assert_eq!(amount_to_ui_amount_string(15, 0), "15");
+  assert_eq!(amount_to_ui_amount_string_trimmed(1_500_000, 6), "1.5");
+  assert_eq!(amount_to_ui_amount_string_trimmed(100_000_000, 6), "100");
+  assert_eq!(amount_to_ui_amount_string_trimmed(0, 6), "0");
+
+  assert_eq!(try_ui_amount_into_amount("1.5", 6).unwrap(), 1_500_000);
+  assert_eq!(try_ui_amount_into_amount("100", 6).unwrap(), 100_000_000);
+  assert_eq!(try_ui_amount_into_amount(".000001", 6).unwrap(), 1);
+  assert_eq!(try_ui_amount_into_amount("1.500000000", 6).unwrap(), 1_500_000);
+  assert!(try_ui_amount_into_amount("0.0000001", 6).is_err());
+  assert!(try_ui_amount_into_amount("1.2.3", 6).is_err());
+  assert!(try_ui_amount_into_amount("", 6).is_err());
+  assert!(try_ui_amount_into_amount("-1", 6).is_err());
+  assert!(try_ui_amount_into_amount("+1", 6).is_err());
+  assert!(try_ui_amount_into_amount("1.+5", 6).is_err());
+}
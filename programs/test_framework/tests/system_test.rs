@@ -1,5 +1,3 @@
-pub mod framework;
-
 use solana_program_test::{
   ProgramTest,
 };
@@ -7,16 +5,35 @@ use solana_sdk::{
   signer::{
     Signer,
   },
+  system_instruction,
+  transaction::{
+    TransactionError,
+  },
 };
 
-use crate::framework::{
+use solana_support_library::{
   account::{
     get_account,
   },
+  context::{
+    execute_signed_transaction,
+    get_payer,
+    refresh_blockhash,
+    try_execute_signed_transaction,
+  },
   system::{
+    advance_nonce_account,
     airdrop_lamport,
+    authorize_nonce_account,
+    create_nonce_account,
+    create_nonce_transaction,
     get_account_balance,
+    get_nonce,
+    get_nonce_data,
     transfer_lamport,
+    try_advance_nonce_account,
+    try_get_nonce_data,
+    withdraw_nonce_account,
   },
 };
 
@@ -48,3 +65,44 @@ async fn transfer_lamport_test() {
   assert!(default_account_balance == 1_500_000_000, "default_account_balance invalid");
   assert!(account_1_balance == 500_000_000, "account_1_balance invalid");
 }
+
+#[tokio::test]
+async fn nonce_account_test() {
+  let mut context = ProgramTest::default().start_with_context().await;
+  let payer = get_payer(&context);
+  let nonce_account = get_account(1);
+  let account_2 = get_account(2);
+  let account_3 = get_account(3);
+  let account_4 = get_account(4);
+
+  create_nonce_account(&mut context, &nonce_account, &account_2.pubkey()).await;
+  let nonce_data = get_nonce_data(&mut context, &nonce_account.pubkey()).await;
+  assert_eq!(nonce_data.authority, account_2.pubkey());
+
+  // a nonce can only be advanced once the bank moved past the blockhash it was derived from
+  refresh_blockhash(&mut context).await;
+  let nonce = get_nonce(&mut context, &nonce_account.pubkey()).await;
+  let transfer_ixn = system_instruction::transfer(&payer.pubkey(), &account_3.pubkey(), 10_000_000);
+  let nonce_txn = create_nonce_transaction(&payer, &nonce_account.pubkey(), &account_2, &nonce, &[transfer_ixn], &[]);
+  assert_eq!(nonce_txn.message.recent_blockhash, nonce);
+  // the banks server cannot process a transaction using the current nonce
+  assert!(try_execute_signed_transaction(&mut context, nonce_txn.clone()).await.is_err());
+
+  // a transaction signed against a consumed nonce is rejected
+  advance_nonce_account(&mut context, &nonce_account.pubkey(), &account_2).await;
+  assert_ne!(get_nonce(&mut context, &nonce_account.pubkey()).await, nonce);
+  let outcome = execute_signed_transaction(&mut context, nonce_txn).await;
+  assert_eq!(outcome.result, Err(TransactionError::BlockhashNotFound));
+  let account_3_balance = get_account_balance(&mut context, &account_3.pubkey()).await;
+  assert_eq!(account_3_balance, 0);
+
+  authorize_nonce_account(&mut context, &nonce_account.pubkey(), &account_2, &account_3.pubkey()).await;
+  refresh_blockhash(&mut context).await;
+  assert!(try_advance_nonce_account(&mut context, &nonce_account.pubkey(), &account_2).await.is_err());
+
+  let nonce_balance = get_account_balance(&mut context, &nonce_account.pubkey()).await;
+  withdraw_nonce_account(&mut context, &nonce_account.pubkey(), &account_3, &account_4.pubkey(), nonce_balance).await;
+  let account_4_balance = get_account_balance(&mut context, &account_4.pubkey()).await;
+  assert_eq!(account_4_balance, nonce_balance);
+  assert!(try_get_nonce_data(&mut context, &nonce_account.pubkey()).await.is_err());
+}
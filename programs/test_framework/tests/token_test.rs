@@ -1,30 +1,94 @@
-pub mod framework;
-
 use solana_program_test::{
+  processor,
   ProgramTest,
 };
 use solana_sdk::{
+  pubkey::{
+    Pubkey,
+  },
+  signature::{
+    Keypair,
+  },
   signer::{
     Signer,
   },
 };
+use spl_token_2022::{
+  processor::{
+    Processor as Token2022Processor,
+  },
+};
 
-use crate::framework::{
+use solana_support_library::{
   account::{
     get_account,
     get_token_account_named,
     TokenName,
   },
+  context::{
+    assert_instruction_error,
+    get_rent,
+  },
+  spl_token::{
+    AuthorityType,
+  },
+  spl_token_2022::{
+    ID as TOKEN_2022_PROGRAM_ID,
+  },
   system::{
     airdrop_lamport,
+    get_account_balance,
+    get_account_type,
   },
   token::{
+    amount_to_ui_amount,
+    approve_token,
+    assert_token_decimals,
+    assert_token_delegate,
+    assert_token_delegated_amount,
+    assert_token_freeze_authority,
+    assert_token_mint_authority,
+    assert_token_supply,
+    burn_token,
+    burn_token_checked,
+    burn_token_ui,
+    change_mint_authority,
+    close_token_account_with_destination,
     create_token_mint,
+    create_token_mint_with_program_id,
     create_associated_token_account,
+    create_associated_token_account_idempotent,
+    create_token_account,
+    create_token_multisig,
+    create_token_multisig_with_program_id,
     get_associated_token_account_address,
+    get_associated_token_account_address_with_program_id,
     get_token_account_balance,
+    get_token_account_data_size,
+    get_token_account_info,
+    get_token_account_ui_balance,
+    get_token_mint_info,
+    get_token_multisig_info,
+    get_token_program_id,
+    get_token_supply,
+    get_wrapped_sol_balance,
     mint_token,
+    mint_token_checked,
+    mint_token_ui,
+    recover_nested_associated_token_account,
+    set_close_authority,
+    set_token_account_owner,
+    top_up_wrapped_sol,
     transfer_token,
+    transfer_token_checked,
+    transfer_token_ui,
+    TokenAuthority,
+    try_transfer_token,
+    try_close_token_account,
+    try_transfer_token_checked,
+    ui_amount_to_amount,
+    unwrap_sol,
+    wrap_sol,
   },
 };
 
@@ -68,3 +132,338 @@ async fn transfer_token_test() {
   assert!(account_2_token_balance == 400_000_000, "account_2_token_balance invalid");
   assert!(account_3_token_balance == 100_000_000, "account_3_token_balance invalid");
 }
+
+#[tokio::test]
+async fn transfer_token_insufficient_funds_test() {
+  let mut context = ProgramTest::default().start_with_context().await;
+  let default_account = get_account(0);
+  airdrop_lamport(&mut context, &default_account.pubkey(),1_000_000_000).await;
+
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+  create_token_mint(&mut context, &token_mint_account, 6, &default_account.pubkey(), None).await;
+
+  let account_2 = get_account(2);
+  let account_2_token_address = create_associated_token_account(&mut context, &account_2.pubkey(), &token_mint_account.pubkey()).await;
+  mint_token(&mut context, &default_account, &token_mint_account.pubkey(), &account_2_token_address, 100_000_000).await;
+
+  let account_3 = get_account(3);
+  let account_3_token_address = create_associated_token_account(&mut context, &account_3.pubkey(), &token_mint_account.pubkey()).await;
+  let txn_result = try_transfer_token(&mut context, &account_2, &account_2_token_address, &account_3_token_address, 200_000_000).await;
+  assert_instruction_error(
+    txn_result,
+    0,
+    1, // TokenError::InsufficientFunds
+  );
+
+  let account_2_token_balance = get_token_account_balance(&mut context, &account_2_token_address).await;
+  assert!(account_2_token_balance == 100_000_000, "account_2_token_balance invalid");
+}
+
+#[tokio::test]
+async fn checked_token_instructions_test() {
+  let mut context = ProgramTest::default().start_with_context().await;
+  let default_account = get_account(0);
+  airdrop_lamport(&mut context, &default_account.pubkey(),1_000_000_000).await;
+
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+  create_token_mint(&mut context, &token_mint_account, 6, &default_account.pubkey(), None).await;
+
+  let account_2 = get_account(2);
+  mint_token_checked(&mut context, &default_account, &token_mint_account.pubkey(), &account_2.pubkey(), 500_000_000, 6).await;
+  let account_2_token_address = get_associated_token_account_address(&account_2.pubkey(), &token_mint_account.pubkey());
+
+  let account_3 = get_account(3);
+  let txn_result = try_transfer_token_checked(&mut context, &account_2, &account_2_token_address, &account_3.pubkey(), 100_000_000, 9).await;
+  assert_instruction_error(
+    txn_result,
+    1,
+    18, // TokenError::MintDecimalsMismatch
+  );
+  transfer_token_checked(&mut context, &account_2, &account_2_token_address, &account_3.pubkey(), 100_000_000, 6).await;
+  burn_token_checked(&mut context, &account_2, &account_2_token_address, &token_mint_account.pubkey(), 50_000_000, 6).await;
+
+  let account_2_token_balance = get_token_account_balance(&mut context, &account_2_token_address).await;
+  let account_3_token_address = get_associated_token_account_address(&account_3.pubkey(), &token_mint_account.pubkey());
+  let account_3_token_balance = get_token_account_balance(&mut context, &account_3_token_address).await;
+  assert!(account_2_token_balance == 350_000_000, "account_2_token_balance invalid");
+  assert!(account_3_token_balance == 100_000_000, "account_3_token_balance invalid");
+}
+
+#[tokio::test]
+async fn token_amount_conversion_test() {
+  let mut context = ProgramTest::default().start_with_context().await;
+  let default_account = get_account(0);
+
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+  create_token_mint(&mut context, &token_mint_account, 6, &default_account.pubkey(), None).await;
+
+  let ui_amount = amount_to_ui_amount(&mut context, &token_mint_account.pubkey(), 1_500_000).await;
+  assert!(ui_amount == "1.5", "ui_amount invalid");
+  let amount = ui_amount_to_amount(&mut context, &token_mint_account.pubkey(), "2.25").await;
+  assert!(amount == 2_250_000, "amount invalid");
+  let data_size = get_token_account_data_size(&mut context, &token_mint_account.pubkey()).await;
+  assert!(data_size == 165, "data_size invalid");
+}
+
+#[tokio::test]
+async fn multisig_authority_test() {
+  let mut context = ProgramTest::default().start_with_context().await;
+  let signer_1 = get_account(3);
+  let signer_2 = get_account(4);
+  let signer_3 = get_account(5);
+
+  let multisig_account = Keypair::new();
+  create_token_multisig(&mut context, &multisig_account, &[&signer_1.pubkey(), &signer_2.pubkey(), &signer_3.pubkey()], 2).await;
+  let multisig_info = get_token_multisig_info(&mut context, &multisig_account.pubkey()).await;
+  assert_eq!(multisig_info.m, 2);
+  assert_eq!(multisig_info.n, 3);
+  assert_eq!(multisig_info.valid_signers(), &[signer_1.pubkey(), signer_2.pubkey(), signer_3.pubkey()]);
+
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+  create_token_mint(&mut context, &token_mint_account, 6, &multisig_account.pubkey(), None).await;
+
+  let account_2 = get_account(2);
+  let account_2_token_address = create_associated_token_account(&mut context, &account_2.pubkey(), &token_mint_account.pubkey()).await;
+  let multisig_address = multisig_account.pubkey();
+  let multisig_signers = [&signer_1, &signer_3];
+  let multisig_authority = TokenAuthority::Multisig(&multisig_address, &multisig_signers);
+  mint_token(&mut context, multisig_authority, &token_mint_account.pubkey(), &account_2_token_address, 100_000_000).await;
+
+  let account_2_token_balance = get_token_account_balance(&mut context, &account_2_token_address).await;
+  assert_eq!(account_2_token_balance, 100_000_000);
+}
+
+#[tokio::test]
+async fn token_2022_test() {
+  let mut program_test = ProgramTest::default();
+  program_test.add_program("spl_token_2022", TOKEN_2022_PROGRAM_ID, processor!(Token2022Processor::process));
+  let mut context = program_test.start_with_context().await;
+  let default_account = get_account(0);
+
+  let token_mint_account = get_token_account_named(TokenName::USDC);
+  create_token_mint_with_program_id(&mut context, &token_mint_account, 6, &default_account.pubkey(), None, &TOKEN_2022_PROGRAM_ID).await;
+  assert_eq!(get_token_program_id(&mut context, &token_mint_account.pubkey()).await, TOKEN_2022_PROGRAM_ID);
+
+  let account_2 = get_account(2);
+  mint_token(&mut context, &default_account, &token_mint_account.pubkey(), &account_2.pubkey(), 100_000_000).await;
+
+  let account_2_token_address = get_associated_token_account_address_with_program_id(&account_2.pubkey(), &token_mint_account.pubkey(), &TOKEN_2022_PROGRAM_ID);
+  assert_eq!(get_token_program_id(&mut context, &account_2_token_address).await, TOKEN_2022_PROGRAM_ID);
+  let account_2_token_balance = get_token_account_balance(&mut context, &account_2_token_address).await;
+  assert_eq!(account_2_token_balance, 100_000_000);
+
+  let signer_1 = get_account(3);
+  let signer_2 = get_account(4);
+  let multisig_account = Keypair::new();
+  create_token_multisig_with_program_id(&mut context, &multisig_account, &[&signer_1.pubkey(), &signer_2.pubkey()], 2, &TOKEN_2022_PROGRAM_ID).await;
+  assert_eq!(get_token_program_id(&mut context, &multisig_account.pubkey()).await, TOKEN_2022_PROGRAM_ID);
+  change_mint_authority(&mut context, &default_account, &token_mint_account.pubkey(), AuthorityType::MintTokens, Some(&multisig_account.pubkey())).await;
+
+  let multisig_address = multisig_account.pubkey();
+  let multisig_signers = [&signer_1, &signer_2];
+  let multisig_authority = TokenAuthority::Multisig(&multisig_address, &multisig_signers);
+  mint_token(&mut context, multisig_authority, &token_mint_account.pubkey(), &account_2.pubkey(), 50_000_000).await;
+  let account_2_token_balance = get_token_account_balance(&mut context, &account_2_token_address).await;
+  assert_eq!(account_2_token_balance, 150_000_000);
+}
+
+#[tokio::test]
+async fn wrapped_sol_test() {
+  let mut context = ProgramTest::default().start_with_context().await;
+  let account_2 = get_account(2);
+  airdrop_lamport(&mut context, &account_2.pubkey(), 3_000_000_000).await;
+
+  let wrapped_sol_address = wrap_sol(&mut context, &account_2, 1_000_000_000).await;
+  let rent = get_rent(&mut context).await;
+  let wrapped_sol_balance = get_wrapped_sol_balance(&mut context, &wrapped_sol_address).await;
+  assert_eq!(wrapped_sol_balance.amount, 1_000_000_000);
+  assert_eq!(wrapped_sol_balance.rent_exempt_reserve, rent.minimum_balance(165));
+
+  top_up_wrapped_sol(&mut context, &account_2, &wrapped_sol_address, 500_000_000).await;
+  wrap_sol(&mut context, &account_2, 500_000_000).await;
+  let wrapped_sol_balance = get_wrapped_sol_balance(&mut context, &wrapped_sol_address).await;
+  assert_eq!(wrapped_sol_balance.amount, 2_000_000_000);
+
+  let account_2_balance = get_account_balance(&mut context, &account_2.pubkey()).await;
+  unwrap_sol(&mut context, &account_2).await;
+  let account_2_balance_after = get_account_balance(&mut context, &account_2.pubkey()).await;
+  assert_eq!(account_2_balance_after - account_2_balance, 2_000_000_000 + rent.minimum_balance(165));
+}
+
+#[tokio::test]
+async fn associated_token_account_idempotent_test() {
+  let mut context = ProgramTest::default().start_with_context().await;
+  let default_account = get_account(0);
+
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+  create_token_mint(&mut context, &token_mint_account, 6, &default_account.pubkey(), None).await;
+
+  let account_2 = get_account(2);
+  mint_token(&mut context, &default_account, &token_mint_account.pubkey(), &account_2.pubkey(), 100_000_000).await;
+  mint_token(&mut context, &default_account, &token_mint_account.pubkey(), &account_2.pubkey(), 50_000_000).await;
+  let account_2_token_address = create_associated_token_account_idempotent(&mut context, &account_2.pubkey(), &token_mint_account.pubkey()).await;
+
+  let account_2_token_balance = get_token_account_balance(&mut context, &account_2_token_address).await;
+  assert_eq!(account_2_token_balance, 150_000_000);
+}
+
+#[tokio::test]
+async fn recover_nested_associated_token_account_test() {
+  let mut context = ProgramTest::default().start_with_context().await;
+  let default_account = get_account(0);
+
+  let owner_token_mint_account = get_token_account_named(TokenName::USDT);
+  create_token_mint(&mut context, &owner_token_mint_account, 6, &default_account.pubkey(), None).await;
+  let nested_token_mint_account = get_token_account_named(TokenName::USDC);
+  create_token_mint(&mut context, &nested_token_mint_account, 6, &default_account.pubkey(), None).await;
+
+  let account_2 = get_account(2);
+  airdrop_lamport(&mut context, &account_2.pubkey(), 1_000_000_000).await;
+  let owner_token_address = create_associated_token_account(&mut context, &account_2.pubkey(), &owner_token_mint_account.pubkey()).await;
+  let nested_token_address = create_associated_token_account(&mut context, &owner_token_address, &nested_token_mint_account.pubkey()).await;
+  mint_token(&mut context, &default_account, &nested_token_mint_account.pubkey(), &nested_token_address, 100_000_000).await;
+
+  let account_2_token_address = create_associated_token_account(&mut context, &account_2.pubkey(), &nested_token_mint_account.pubkey()).await;
+  recover_nested_associated_token_account(&mut context, &account_2, &owner_token_mint_account.pubkey(), &nested_token_mint_account.pubkey()).await;
+
+  let account_2_token_balance = get_token_account_balance(&mut context, &account_2_token_address).await;
+  assert_eq!(account_2_token_balance, 100_000_000);
+  assert_eq!(get_account_type(&mut context, &nested_token_address).await, 0);
+}
+
+#[tokio::test]
+async fn ui_amount_token_helpers_test() {
+  let mut context = ProgramTest::default().start_with_context().await;
+  let default_account = get_account(0);
+  airdrop_lamport(&mut context, &default_account.pubkey(), 1_000_000_000).await;
+
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+  create_token_mint(&mut context, &token_mint_account, 6, &default_account.pubkey(), None).await;
+
+  let account_2 = get_account(2);
+  mint_token_ui(&mut context, &default_account, &token_mint_account.pubkey(), &default_account.pubkey(), "100").await;
+  let default_token_address = get_associated_token_account_address(&default_account.pubkey(), &token_mint_account.pubkey());
+  transfer_token_ui(&mut context, &default_account, &default_token_address, &account_2.pubkey(), "12.5").await;
+  burn_token_ui(&mut context, &default_account, &default_token_address, &token_mint_account.pubkey(), "0.25").await;
+
+  let ui_balance = get_token_account_ui_balance(&mut context, &default_token_address).await;
+  assert_eq!(ui_balance.amount, "87250000");
+  assert_eq!(ui_balance.decimals, 6);
+  assert_eq!(ui_balance.ui_amount, Some(87.25));
+  assert_eq!(ui_balance.ui_amount_string, "87.25");
+
+  let account_2_token_address = get_associated_token_account_address(&account_2.pubkey(), &token_mint_account.pubkey());
+  let account_2_ui_balance = get_token_account_ui_balance(&mut context, &account_2_token_address).await;
+  assert_eq!(account_2_ui_balance.ui_amount_string, "12.5");
+}
+
+#[tokio::test]
+async fn token_mint_info_test() {
+  let mut context = ProgramTest::default().start_with_context().await;
+  let default_account = get_account(0);
+  airdrop_lamport(&mut context, &default_account.pubkey(), 1_000_000_000).await;
+  let freeze_account = get_account(1);
+
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+  create_token_mint(&mut context, &token_mint_account, 6, &default_account.pubkey(), Some(&freeze_account.pubkey())).await;
+  assert_token_decimals(&mut context, &token_mint_account.pubkey(), 6).await;
+  assert_token_mint_authority(&mut context, &token_mint_account.pubkey(), Some(&default_account.pubkey())).await;
+  assert_token_freeze_authority(&mut context, &token_mint_account.pubkey(), Some(&freeze_account.pubkey())).await;
+
+  mint_token(&mut context, &default_account, &token_mint_account.pubkey(), &default_account.pubkey(), 100_000_000).await;
+  let default_token_address = get_associated_token_account_address(&default_account.pubkey(), &token_mint_account.pubkey());
+  burn_token(&mut context, &default_account, &default_token_address, &token_mint_account.pubkey(), 40_000_000).await;
+  assert_token_supply(&mut context, &token_mint_account.pubkey(), 60_000_000).await;
+  assert_eq!(get_token_supply(&mut context, &token_mint_account.pubkey()).await, 60_000_000);
+
+  let account_3 = get_account(3);
+  change_mint_authority(&mut context, &default_account, &token_mint_account.pubkey(), AuthorityType::MintTokens, Some(&account_3.pubkey())).await;
+  change_mint_authority(&mut context, &freeze_account, &token_mint_account.pubkey(), AuthorityType::FreezeAccount, None).await;
+  assert_token_mint_authority(&mut context, &token_mint_account.pubkey(), Some(&account_3.pubkey())).await;
+  assert_token_freeze_authority(&mut context, &token_mint_account.pubkey(), None).await;
+
+  let token_mint = get_token_mint_info(&mut context, &token_mint_account.pubkey()).await;
+  assert!(token_mint.is_initialized);
+}
+
+#[tokio::test]
+async fn delegate_token_test() {
+  let mut context = ProgramTest::default().start_with_context().await;
+  let default_account = get_account(0);
+
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+  create_token_mint(&mut context, &token_mint_account, 6, &default_account.pubkey(), None).await;
+
+  let account_2 = get_account(2);
+  let account_2_token_address = create_associated_token_account(&mut context, &account_2.pubkey(), &token_mint_account.pubkey()).await;
+  mint_token(&mut context, &default_account, &token_mint_account.pubkey(), &account_2_token_address, 100_000_000).await;
+  let account_3 = get_account(3);
+  let account_3_token_address = create_associated_token_account(&mut context, &account_3.pubkey(), &token_mint_account.pubkey()).await;
+
+  let delegate_account = get_account(4);
+  approve_token(&mut context, &account_2, &account_2_token_address, &delegate_account.pubkey(), 50_000_000).await;
+  assert_token_delegate(&mut context, &account_2_token_address, Some(&delegate_account.pubkey())).await;
+  assert_token_delegated_amount(&mut context, &account_2_token_address, 50_000_000).await;
+
+  transfer_token(&mut context, &delegate_account, &account_2_token_address, &account_3_token_address, 15_000_000).await;
+  assert_token_delegated_amount(&mut context, &account_2_token_address, 35_000_000).await;
+  burn_token(&mut context, &delegate_account, &account_2_token_address, &token_mint_account.pubkey(), 10_000_000).await;
+  assert_token_delegated_amount(&mut context, &account_2_token_address, 25_000_000).await;
+
+  let txn_result = try_transfer_token(&mut context, &delegate_account, &account_2_token_address, &account_3_token_address, 30_000_000).await;
+  assert_instruction_error(
+    txn_result,
+    0,
+    1, // TokenError::InsufficientFunds
+  );
+
+  transfer_token(&mut context, &delegate_account, &account_2_token_address, &account_3_token_address, 25_000_000).await;
+  assert_token_delegate(&mut context, &account_2_token_address, None).await;
+  assert_token_delegated_amount(&mut context, &account_2_token_address, 0).await;
+  let txn_result = try_transfer_token(&mut context, &delegate_account, &account_2_token_address, &account_3_token_address, 1_000_000).await;
+  assert_instruction_error(
+    txn_result,
+    0,
+    4, // TokenError::OwnerMismatch
+  );
+
+  let account_2_token_balance = get_token_account_balance(&mut context, &account_2_token_address).await;
+  assert_eq!(account_2_token_balance, 50_000_000);
+  let account_3_token_balance = get_token_account_balance(&mut context, &account_3_token_address).await;
+  assert_eq!(account_3_token_balance, 40_000_000);
+}
+
+#[tokio::test]
+async fn token_account_authority_test() {
+  let mut context = ProgramTest::default().start_with_context().await;
+  let default_account = get_account(0);
+
+  let token_mint_account = get_token_account_named(TokenName::USDT);
+  create_token_mint(&mut context, &token_mint_account, 6, &default_account.pubkey(), None).await;
+
+  let account_2 = get_account(2);
+  let token_account = Keypair::new();
+  create_token_account(&mut context, &account_2.pubkey(), &token_mint_account.pubkey(), &token_account).await;
+
+  let account_3 = get_account(3);
+  let account_4 = get_account(4);
+  set_close_authority(&mut context, &account_2, &token_account.pubkey(), Some(&account_3.pubkey())).await;
+  set_token_account_owner(&mut context, &account_2, &token_account.pubkey(), &account_4.pubkey()).await;
+  let token_account_info = get_token_account_info(&mut context, &token_account.pubkey()).await;
+  assert_eq!(token_account_info.owner, account_4.pubkey());
+  assert_eq!(Option::<Pubkey>::from(token_account_info.close_authority), Some(account_3.pubkey()));
+
+  let txn_result = try_close_token_account(&mut context, &account_2, &token_account.pubkey()).await;
+  assert_instruction_error(
+    txn_result,
+    0,
+    4, // TokenError::OwnerMismatch
+  );
+
+  let account_5 = get_account(5);
+  let account_5_balance = get_account_balance(&mut context, &account_5.pubkey()).await;
+  let token_account_balance = get_account_balance(&mut context, &token_account.pubkey()).await;
+  close_token_account_with_destination(&mut context, &account_3, &token_account.pubkey(), &account_5.pubkey()).await;
+  assert_eq!(get_account_balance(&mut context, &account_5.pubkey()).await, account_5_balance + token_account_balance);
+  assert_eq!(get_account_type(&mut context, &token_account.pubkey()).await, 0);
+}